@@ -0,0 +1,74 @@
+use aoc_common::Day;
+use aoc_common::Solution;
+use criterion::criterion_group;
+use criterion::criterion_main;
+use criterion::BenchmarkId;
+use criterion::Criterion;
+use std::fs;
+use std::path::Path;
+
+/// Benchmarks each stage of `S` against `input`, labelled `label` within the
+/// day's group.
+fn bench_stages<S: Solution>(c: &mut Criterion, day: &str, label: &str, input: &str) {
+    let mut group = c.benchmark_group(day);
+    if label != "input" {
+        group.sample_size(10);
+    }
+    group.bench_with_input(BenchmarkId::new("parse", label), input, |b, input| {
+        b.iter(|| S::parse(input).unwrap())
+    });
+    let parsed = S::parse(input).unwrap();
+    group.bench_with_input(BenchmarkId::new("part1", label), &parsed, |b, parsed| {
+        b.iter(|| S::part1(parsed))
+    });
+    group.bench_with_input(BenchmarkId::new("part2", label), &parsed, |b, parsed| {
+        b.iter(|| S::part2(parsed))
+    });
+    group.finish();
+}
+
+fn checked_in(day: &str) -> String {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("..")
+        .join(day)
+        .join("input");
+    fs::read_to_string(&path).unwrap_or_else(|err| panic!("reading {}: {err}", path.display()))
+}
+
+/// Benchmarks `S` against a generated input of each of `sizes`, in the day's
+/// generator units.
+fn bench_generated<S: Solution>(c: &mut Criterion, day: &Day, sizes: &[usize]) {
+    for &size in sizes {
+        let input = day.generate(2022, size);
+        bench_stages::<S>(c, day.name, &format!("gen{size}"), &input);
+    }
+}
+
+fn checked_in_inputs(c: &mut Criterion) {
+    bench_stages::<one::One>(c, "one", "input", &checked_in("one"));
+    bench_stages::<two::Two>(c, "two", "input", &checked_in("two"));
+    bench_stages::<three::Three>(c, "three", "input", &checked_in("three"));
+    bench_stages::<four::Four>(c, "four", "input", &checked_in("four"));
+    bench_stages::<five::Five>(c, "five", "input", &checked_in("five"));
+    bench_stages::<six::Six>(c, "six", "input", &checked_in("six"));
+    bench_stages::<seven::Seven>(c, "seven", "input", &checked_in("seven"));
+    bench_stages::<eight::Eight>(c, "eight", "input", &checked_in("eight"));
+    bench_stages::<nine::Nine>(c, "nine", "input", &checked_in("nine"));
+    bench_stages::<ten::Ten>(c, "ten", "input", &checked_in("ten"));
+}
+
+fn generated_inputs(c: &mut Criterion) {
+    bench_generated::<one::One>(c, &one::DAY, &[10_000, 100_000]);
+    bench_generated::<two::Two>(c, &two::DAY, &[100_000, 1_000_000]);
+    bench_generated::<three::Three>(c, &three::DAY, &[100_000, 1_000_000]);
+    bench_generated::<four::Four>(c, &four::DAY, &[100_000, 1_000_000]);
+    bench_generated::<five::Five>(c, &five::DAY, &[10_000, 100_000]);
+    bench_generated::<six::Six>(c, &six::DAY, &[100_000, 1_000_000]);
+    bench_generated::<seven::Seven>(c, &seven::DAY, &[1_000, 4_000]);
+    bench_generated::<eight::Eight>(c, &eight::DAY, &[300, 1_000]);
+    bench_generated::<nine::Nine>(c, &nine::DAY, &[10_000, 100_000]);
+    bench_generated::<ten::Ten>(c, &ten::DAY, &[100_000, 1_000_000]);
+}
+
+criterion_group!(benches, checked_in_inputs, generated_inputs);
+criterion_main!(benches);
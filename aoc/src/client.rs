@@ -0,0 +1,98 @@
+use anyhow::anyhow;
+use anyhow::bail;
+use anyhow::Context;
+use anyhow::Result;
+use clap::Args;
+use std::env;
+use std::io::Read;
+use std::time::Duration;
+use tracing::info;
+
+const YEAR: u32 = 2022;
+const USER_AGENT: &str = "advent-of-code-2022 aoc runner (ureq)";
+/// Environment variable holding the `session` cookie of a logged-in browser.
+pub const SESSION_VAR: &str = "AOC_SESSION";
+
+/// Where the puzzle site lives, so a local stand-in can replace it.
+#[derive(Debug, Clone, Args)]
+pub struct ClientOpts {
+    #[clap(
+        long,
+        env = "AOC_BASE_URL",
+        default_value = "https://adventofcode.com",
+        help = "Base URL of the puzzle site"
+    )]
+    pub base_url: String,
+}
+
+/// A logged-in client for the puzzle site.
+pub struct Client {
+    agent: ureq::Agent,
+    base_url: String,
+    session: String,
+}
+
+impl Client {
+    pub fn new(opts: &ClientOpts) -> Result<Self> {
+        let session = env::var(SESSION_VAR)
+            .ok()
+            .filter(|session| !session.trim().is_empty())
+            .ok_or_else(|| anyhow!("set {SESSION_VAR} to your adventofcode.com session cookie"))?;
+        let agent = ureq::AgentBuilder::new()
+            .timeout(Duration::from_secs(30))
+            .user_agent(USER_AGENT)
+            .build();
+        Ok(Self {
+            agent,
+            base_url: opts.base_url.trim_end_matches('/').to_string(),
+            session: session.trim().to_string(),
+        })
+    }
+
+    fn url(&self, day: u32, path: &str) -> String {
+        format!("{}/{YEAR}/day/{day}{path}", self.base_url)
+    }
+
+    fn cookie(&self) -> String {
+        format!("session={}", self.session)
+    }
+
+    /// Downloads the puzzle input for `day`.
+    pub fn input(&self, day: u32) -> Result<String> {
+        let url = self.url(day, "/input");
+        info!(day, %url, "downloading input");
+        let response = self.agent.get(&url).set("Cookie", &self.cookie()).call();
+        read_body(&url, day, response)
+    }
+
+    /// Posts `answer` for `part` of `day`, returning the response page.
+    pub fn submit(&self, day: u32, part: u8, answer: &str) -> Result<String> {
+        let url = self.url(day, "/answer");
+        let level = part.to_string();
+        info!(day, part, answer, %url, "submitting answer");
+        let response = self
+            .agent
+            .post(&url)
+            .set("Cookie", &self.cookie())
+            .send_form(&[("level", &level), ("answer", answer)]);
+        read_body(&url, day, response)
+    }
+}
+
+fn read_body(url: &str, day: u32, response: Result<ureq::Response, ureq::Error>) -> Result<String> {
+    let response = match response {
+        Ok(response) => response,
+        Err(ureq::Error::Status(400 | 401, _)) => {
+            bail!("{url}: the session token was rejected, check {SESSION_VAR}")
+        }
+        Err(ureq::Error::Status(404, _)) => bail!("{url}: day {day} is not unlocked yet"),
+        Err(ureq::Error::Status(code, _)) => bail!("{url}: server returned {code}"),
+        Err(err) => return Err(err).with_context(|| format!("requesting {url}")),
+    };
+    let mut body = String::new();
+    response
+        .into_reader()
+        .read_to_string(&mut body)
+        .with_context(|| format!("reading {url}"))?;
+    Ok(body)
+}
@@ -0,0 +1,59 @@
+use anyhow::Context;
+use anyhow::Result;
+use aoc_common::Day;
+use aoc_common::InputSource;
+use std::fs;
+use std::path::PathBuf;
+use tracing::debug;
+
+/// Puzzle inputs downloaded by `aoc fetch`, kept at `<dir>/<day>/input`
+/// alongside anything else recorded per day.
+#[derive(Debug, Clone)]
+pub struct InputCache {
+    dir: PathBuf,
+}
+
+impl InputCache {
+    pub fn new(dir: PathBuf) -> Self {
+        Self { dir }
+    }
+
+    pub fn day_dir(&self, day: &Day) -> PathBuf {
+        self.dir.join(day.name)
+    }
+
+    pub fn path(&self, day: &Day) -> PathBuf {
+        self.day_dir(day).join("input")
+    }
+
+    /// The cached input for `day`, if it has been fetched.
+    pub fn get(&self, day: &Day) -> Option<InputSource> {
+        let path = self.path(day);
+        path.is_file().then_some(InputSource::Path(path))
+    }
+
+    /// The input to run `day` on when none is given: the fetched one if there
+    /// is one, otherwise the checked-in one.
+    pub fn input_for(&self, day: &Day) -> InputSource {
+        match self.get(day) {
+            Some(cached) => {
+                debug!(day = day.number, input = %cached, "using cached input");
+                cached
+            }
+            None => day.default_input(),
+        }
+    }
+
+    /// Saves `input` for `day`. It's written aside first so an interrupted
+    /// download never leaves a truncated input behind.
+    pub fn store(&self, day: &Day, input: &str) -> Result<PathBuf> {
+        let path = self.path(day);
+        let parent = path.parent().expect("cache paths have a day directory");
+        fs::create_dir_all(parent).with_context(|| format!("creating {}", parent.display()))?;
+        let partial = path.with_extension("partial");
+        fs::write(&partial, input).with_context(|| format!("writing {}", partial.display()))?;
+        fs::rename(&partial, &path).with_context(|| format!("writing {}", path.display()))?;
+        debug!(day = day.number, path = %path.display(), bytes = input.len(), "cached input");
+        Ok(path)
+    }
+}
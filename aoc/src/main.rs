@@ -0,0 +1,293 @@
+use anyhow::anyhow;
+use anyhow::bail;
+use anyhow::Context;
+use anyhow::Result;
+use aoc_common::InputOpts;
+use aoc_common::LogOpts;
+use aoc_common::Rng;
+use aoc_common::RunOpts;
+use clap::Parser;
+use clap::Subcommand;
+use client::Client;
+use client::ClientOpts;
+use inputs::InputCache;
+use render::RenderOpts;
+use results::ResultCache;
+use serve::ServeOpts;
+use std::fs::File;
+use std::io;
+use std::io::BufWriter;
+use std::io::Write;
+use std::path::Path;
+use std::path::PathBuf;
+use tracing::info_span;
+
+mod client;
+mod inputs;
+mod registry;
+mod render;
+mod repl;
+mod results;
+mod run_all;
+mod scaffold;
+mod serve;
+mod submit;
+mod verify;
+
+#[derive(Debug, Parser)]
+#[clap(about = "Advent of Code 2022 runner")]
+struct Opts {
+    #[clap(subcommand)]
+    command: Command,
+    #[clap(
+        long,
+        global = true,
+        env = "AOC_CACHE_DIR",
+        default_value = ".aoc-cache",
+        help = "Where fetched inputs are kept"
+    )]
+    cache_dir: PathBuf,
+    #[clap(flatten)]
+    log: LogOpts,
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    #[clap(about = "Run one day, or every day with `all`")]
+    Run {
+        #[clap(help = "Day number or name, or `all`")]
+        day: String,
+        #[clap(flatten)]
+        input: InputOpts,
+        #[clap(flatten)]
+        run: RunOpts,
+        #[clap(
+            long,
+            help = "Solve again instead of reusing answers from earlier runs"
+        )]
+        no_cache: bool,
+    },
+    #[clap(about = "Manage the answers kept from earlier runs")]
+    Cache {
+        #[clap(subcommand)]
+        command: CacheCommand,
+    },
+    #[clap(about = "List the registered days")]
+    List,
+    #[clap(about = "Check every day against its recorded answers")]
+    Verify,
+    #[clap(about = "Generate a random valid input for a day")]
+    Gen {
+        #[clap(help = "Day number or name")]
+        day: String,
+        #[clap(
+            long,
+            default_value_t = 1000,
+            help = "How much to generate, in the day's units (see `aoc list`)"
+        )]
+        size: usize,
+        #[clap(
+            long,
+            default_value_t = 2022,
+            help = "RNG seed, the same seed gives the same input"
+        )]
+        seed: u64,
+        #[clap(short, long, help = "Write to a file instead of stdout")]
+        output: Option<PathBuf>,
+    },
+    #[clap(about = "Download a day's input into the cache, using the AOC_SESSION token")]
+    Fetch {
+        #[clap(help = "Day number or name")]
+        day: String,
+        #[clap(long, help = "Download again even if the input is already cached")]
+        force: bool,
+        #[clap(flatten)]
+        client: ClientOpts,
+    },
+    #[clap(about = "Solve a part and submit the answer, using the AOC_SESSION token")]
+    Submit {
+        #[clap(help = "Day number or name")]
+        day: String,
+        #[clap(value_parser = clap::value_parser!(u8).range(1..=2), help = "Part to submit, 1 or 2")]
+        part: u8,
+        #[clap(long, help = "Submit this instead of the computed answer")]
+        answer: Option<String>,
+        #[clap(flatten)]
+        input: InputOpts,
+        #[clap(flatten)]
+        client: ClientOpts,
+    },
+    #[clap(about = "Draw a day's puzzle state as PPM or PNG frames or an animated GIF")]
+    Render {
+        #[clap(help = "Day number or name")]
+        day: String,
+        #[clap(flatten)]
+        input: InputOpts,
+        #[clap(flatten)]
+        render: RenderOpts,
+    },
+    #[clap(about = "Load a day's input and explore it a command at a time")]
+    Repl {
+        #[clap(help = "Day number or name")]
+        day: String,
+        #[clap(flatten)]
+        input: InputOpts,
+    },
+    #[clap(about = "Show every day's answers and pictures on a local web page")]
+    Serve {
+        #[clap(flatten)]
+        serve: ServeOpts,
+    },
+    #[clap(about = "Create and register the crate for a new day")]
+    New {
+        #[clap(help = "Day number, 1 to 25")]
+        day: u32,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+enum CacheCommand {
+    #[clap(about = "Forget cached answers; fetched inputs are kept")]
+    Clear {
+        #[clap(help = "Only clear this day, by number or name")]
+        day: Option<String>,
+    },
+}
+
+fn main() -> Result<()> {
+    let opts = Opts::parse();
+    opts.log.init()?;
+    let results = ResultCache::new(opts.cache_dir.clone());
+    let cache = InputCache::new(opts.cache_dir);
+    match opts.command {
+        Command::Run {
+            day,
+            input,
+            run,
+            no_cache,
+        } if day == "all" => {
+            if input.source().is_some() {
+                bail!("an input cannot be given with `run all`");
+            }
+            if run.animate {
+                bail!("--animate plays one day at a time");
+            }
+            if run.stream {
+                bail!("--stream solves one day at a time");
+            }
+            let results = (!no_cache).then_some(&results);
+            run_all::run_all(registry::DAYS, |day| cache.input_for(day), results, &run)?;
+        }
+        Command::Run {
+            day,
+            input,
+            run,
+            no_cache,
+        } => {
+            let day = registry::find(&day).ok_or_else(|| anyhow!("Unknown day: {day}"))?;
+            let source = input.source().unwrap_or_else(|| cache.input_for(day));
+            if no_cache || run.animate {
+                day.run(&source, &run)?;
+            } else {
+                let _span = info_span!("day", day = day.number).entered();
+                let report = results.solve_source(day, &source, run.part, run.stream)?;
+                day.print(&source, &report, &run)?;
+            }
+        }
+        Command::Cache {
+            command: CacheCommand::Clear { day },
+        } => {
+            let day = match day {
+                Some(day) => {
+                    Some(registry::find(&day).ok_or_else(|| anyhow!("Unknown day: {day}"))?)
+                }
+                None => None,
+            };
+            let removed = results.clear(day)?;
+            println!(
+                "Removed {removed} cached answer{}",
+                if removed == 1 { "" } else { "s" }
+            );
+        }
+        Command::List => {
+            for day in registry::DAYS {
+                print!("{:>2} {:<6} gen size: {}", day.number, day.name, day.unit);
+                if !day.views.is_empty() {
+                    print!("; views: {}", day.views.join(", "));
+                }
+                if day.repl.is_some() {
+                    print!("; repl");
+                }
+                if day.outline.is_some() {
+                    print!("; tree");
+                }
+                println!();
+            }
+        }
+        Command::Verify => verify::verify(registry::DAYS)?,
+        Command::Gen {
+            day,
+            size,
+            seed,
+            output,
+        } => {
+            let day = registry::find(&day).ok_or_else(|| anyhow!("Unknown day: {day}"))?;
+            if size == 0 {
+                bail!("--size must be at least 1");
+            }
+            let mut out: Box<dyn Write> = match &output {
+                Some(path) => Box::new(BufWriter::new(
+                    File::create(path).with_context(|| format!("creating {}", path.display()))?,
+                )),
+                None => Box::new(BufWriter::new(io::stdout().lock())),
+            };
+            (day.generator)(&mut Rng::new(seed), size, &mut out)?;
+            out.flush()?;
+        }
+        Command::Fetch { day, force, client } => {
+            let day = registry::find(&day).ok_or_else(|| anyhow!("Unknown day: {day}"))?;
+            match cache.get(day) {
+                Some(cached) if !force => {
+                    println!("Day {} is already cached at {cached}", day.number)
+                }
+                _ => {
+                    let input = Client::new(&client)?.input(day.number)?;
+                    let path = cache.store(day, &input)?;
+                    println!("Saved day {} input to {}", day.number, path.display());
+                }
+            }
+        }
+        Command::Submit {
+            day,
+            part,
+            answer,
+            input,
+            client,
+        } => {
+            let day = registry::find(&day).ok_or_else(|| anyhow!("Unknown day: {day}"))?;
+            let source = input.source().unwrap_or_else(|| cache.input_for(day));
+            submit::submit(day, part, answer, &source, &cache, &client)?;
+        }
+        Command::Render { day, input, render } => {
+            let day = registry::find(&day).ok_or_else(|| anyhow!("Unknown day: {day}"))?;
+            let source = input.source().unwrap_or_else(|| cache.input_for(day));
+            render::render(day, &source, &render)?;
+        }
+        Command::Repl { day, input } => {
+            let day = registry::find(&day).ok_or_else(|| anyhow!("Unknown day: {day}"))?;
+            let source = input.source().unwrap_or_else(|| cache.input_for(day));
+            repl::repl(day, &source)?;
+        }
+        Command::Serve { serve } => {
+            serve::serve(registry::DAYS, |day| cache.input_for(day), &results, &serve)?;
+        }
+        Command::New { day } => {
+            if registry::find(&day.to_string()).is_some() {
+                bail!("day {day} is already registered");
+            }
+            scaffold::new_day(Path::new("."), day)?;
+            println!("Created day {day}; fill in its example tests, then `aoc fetch {day}`");
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,23 @@
+use aoc_common::Day;
+
+/// Every solved day, in puzzle order.
+pub const DAYS: &[Day] = &[
+    one::DAY,
+    two::DAY,
+    three::DAY,
+    four::DAY,
+    five::DAY,
+    six::DAY,
+    seven::DAY,
+    eight::DAY,
+    nine::DAY,
+    ten::DAY,
+];
+
+/// Looks a day up by number (`7`) or crate name (`seven`).
+pub fn find(key: &str) -> Option<&'static Day> {
+    match key.parse::<u32>() {
+        Ok(number) => DAYS.iter().find(|day| day.number == number),
+        Err(_) => DAYS.iter().find(|day| day.name == key),
+    }
+}
@@ -0,0 +1,187 @@
+use anyhow::anyhow;
+use anyhow::bail;
+use anyhow::Result;
+use aoc_common::in_file;
+use aoc_common::write_png;
+use aoc_common::write_ppm;
+use aoc_common::Day;
+use aoc_common::Frame;
+use aoc_common::GifWriter;
+use aoc_common::InputSource;
+use clap::Args;
+use std::fs::File;
+use std::io;
+use std::io::BufWriter;
+use std::io::Write;
+use std::path::Path;
+use std::path::PathBuf;
+
+#[derive(Debug, Args)]
+pub struct RenderOpts {
+    #[clap(
+        long,
+        help = "What to draw, see `aoc list` (default: the day's first view)"
+    )]
+    view: Option<String>,
+    #[clap(
+        short,
+        long,
+        help = "Image to write, .ppm, .png or .gif; stills get numbered when there are several frames"
+    )]
+    output: PathBuf,
+    #[clap(long, default_value_t = 8, value_parser = clap::value_parser!(u16).range(1..), help = "Pixels per grid cell")]
+    scale: u16,
+    #[clap(long, default_value_t = 20, value_parser = clap::value_parser!(u16).range(1..=100), help = "GIF frames per second")]
+    fps: u16,
+    #[clap(long, default_value_t = 1, value_parser = clap::value_parser!(u64).range(1..), help = "Only keep every Nth frame; the last one is always kept")]
+    every: u64,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Kind {
+    Ppm,
+    Png,
+    Gif,
+}
+
+/// Where kept frames go: one GIF, or still images that are only numbered
+/// once a second frame turns up.
+struct Output<'a> {
+    opts: &'a RenderOpts,
+    kind: Kind,
+    gif: Option<GifWriter<BufWriter<File>>>,
+    first: Option<Frame>,
+    written: usize,
+}
+
+impl Output<'_> {
+    fn keep(&mut self, frame: &Frame) -> io::Result<()> {
+        match self.kind {
+            Kind::Gif => {
+                if self.gif.is_none() {
+                    let out = BufWriter::new(create(&self.opts.output)?);
+                    self.gif = Some(GifWriter::new(
+                        out,
+                        frame.width(),
+                        frame.height(),
+                        self.opts.scale as usize,
+                        self.opts.fps,
+                    )?);
+                }
+                self.gif.as_mut().expect("created above").frame(frame)?;
+                self.written += 1;
+            }
+            _ => match self.first.take() {
+                None if self.written == 0 => self.first = Some(frame.clone()),
+                first => {
+                    if let Some(first) = first {
+                        self.still(&first, Some(1))?;
+                    }
+                    self.still(frame, Some(self.written + 1))?;
+                }
+            },
+        }
+        Ok(())
+    }
+
+    fn still(&mut self, frame: &Frame, number: Option<usize>) -> io::Result<()> {
+        let path = match number {
+            Some(number) => numbered(&self.opts.output, number),
+            None => self.opts.output.clone(),
+        };
+        let mut out = BufWriter::new(create(&path)?);
+        let scale = self.opts.scale as usize;
+        match self.kind {
+            Kind::Png => write_png(frame, scale, &mut out)?,
+            _ => write_ppm(frame, scale, &mut out)?,
+        }
+        self.written += 1;
+        out.flush()
+    }
+
+    fn finish(mut self) -> io::Result<usize> {
+        if let Some(gif) = self.gif.take() {
+            gif.finish()?.flush()?;
+        }
+        if let Some(first) = self.first.take() {
+            self.still(&first, None)?;
+        }
+        Ok(self.written)
+    }
+}
+
+fn create(path: &Path) -> io::Result<File> {
+    File::create(path)
+        .map_err(|err| io::Error::new(err.kind(), format!("creating {}: {err}", path.display())))
+}
+
+/// `frames/rope.png` becomes `frames/rope-00012.png`.
+fn numbered(path: &Path, number: usize) -> PathBuf {
+    let stem = path.file_stem().unwrap_or_default().to_string_lossy();
+    let ext = path.extension().unwrap_or_default().to_string_lossy();
+    path.with_file_name(format!("{stem}-{number:05}.{ext}"))
+}
+
+/// Draws one of the day's views from `source` and writes it out as images.
+pub fn render(day: &Day, source: &InputSource, opts: &RenderOpts) -> Result<()> {
+    let draw = day
+        .render
+        .ok_or_else(|| anyhow!("day {} has nothing to render", day.number))?;
+    let view = match &opts.view {
+        Some(view) if day.views.contains(&view.as_str()) => view.as_str(),
+        Some(view) => bail!(
+            "day {} has no {view:?} view, pick one of: {}",
+            day.number,
+            day.views.join(", ")
+        ),
+        None => day.views[0],
+    };
+    let kind = match opts.output.extension().and_then(|ext| ext.to_str()) {
+        Some("ppm") => Kind::Ppm,
+        Some("png") => Kind::Png,
+        Some("gif") => Kind::Gif,
+        _ => bail!(
+            "can't tell the image format of {}, use .ppm, .png or .gif",
+            opts.output.display()
+        ),
+    };
+    let raw = source.read()?;
+    let mut output = Output {
+        opts,
+        kind,
+        gif: None,
+        first: None,
+        written: 0,
+    };
+    let mut seen = 0;
+    let mut skipped = None;
+    draw(&raw, view, &mut |frame| {
+        seen += 1;
+        if (seen - 1) % opts.every == 0 {
+            skipped = None;
+            output.keep(frame)
+        } else {
+            skipped = Some(frame.clone());
+            Ok(())
+        }
+    })
+    .map_err(|err| in_file(err, source))?;
+    if let Some(last) = skipped {
+        output.keep(&last)?;
+    }
+    let written = output.finish()?;
+    match (written, kind) {
+        (0, _) => println!("Day {} drew nothing", day.number),
+        (1, _) | (_, Kind::Gif) => println!(
+            "Wrote {written} frame{} to {}",
+            if written == 1 { "" } else { "s" },
+            opts.output.display()
+        ),
+        _ => println!(
+            "Wrote {written} frames, {} to {}",
+            numbered(&opts.output, 1).display(),
+            numbered(&opts.output, written).display()
+        ),
+    }
+    Ok(())
+}
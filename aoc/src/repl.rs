@@ -0,0 +1,74 @@
+use anyhow::anyhow;
+use anyhow::bail;
+use anyhow::Result;
+use aoc_common::Day;
+use aoc_common::InputSource;
+use std::io;
+use std::io::BufRead;
+use std::io::IsTerminal;
+use std::io::Write;
+
+const BUILTINS: &[(&str, &str)] = &[
+    ("help", "list the commands"),
+    ("quit", "leave, as does end of input"),
+];
+
+/// Parses `source` and runs the day's commands from stdin until it ends or
+/// `quit`. Answers go to stdout and failed commands to stderr, so a script
+/// of commands can be piped in as well as typed.
+pub fn repl(day: &Day, source: &InputSource) -> Result<()> {
+    let start = day
+        .repl
+        .ok_or_else(|| anyhow!("day {} has no repl", day.number))?;
+    if *source == InputSource::Stdin {
+        bail!("commands are read from stdin, so the input has to come from somewhere else");
+    }
+    let raw = source.read()?;
+    let mut session = start(&raw).map_err(|err| err.with_file(source))?;
+
+    let stdin = io::stdin();
+    let interactive = stdin.is_terminal();
+    let mut out = io::stdout().lock();
+    if interactive {
+        writeln!(
+            out,
+            "Day {} ({}) on {source}, `help` lists the commands",
+            day.number, day.name
+        )?;
+    }
+    let mut line = String::new();
+    loop {
+        if interactive {
+            write!(out, "{}> ", day.name)?;
+            out.flush()?;
+        }
+        line.clear();
+        if stdin.lock().read_line(&mut line)? == 0 {
+            break;
+        }
+        let words = line.split_whitespace().collect::<Vec<_>>();
+        let Some((&command, args)) = words.split_first() else {
+            continue;
+        };
+        match command {
+            "quit" | "exit" => break,
+            "help" => {
+                let width = day
+                    .commands
+                    .iter()
+                    .chain(BUILTINS)
+                    .map(|(usage, _)| usage.len())
+                    .max()
+                    .unwrap_or(0);
+                for (usage, about) in day.commands.iter().chain(BUILTINS) {
+                    writeln!(out, "  {usage:<width$}  {about}")?;
+                }
+            }
+            _ => match session.run(command, args) {
+                Ok(answer) => writeln!(out, "{}", answer.trim_end())?,
+                Err(err) => eprintln!("error: {err:#}"),
+            },
+        }
+    }
+    Ok(())
+}
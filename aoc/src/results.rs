@@ -0,0 +1,240 @@
+use anyhow::Context;
+use anyhow::Result;
+use aoc_common::Answer;
+use aoc_common::Day;
+use aoc_common::InputSource;
+use aoc_common::ParseError;
+use aoc_common::Part;
+use aoc_common::PartReport;
+use aoc_common::Report;
+use sha2::Digest;
+use sha2::Sha256;
+use std::fs;
+use std::io;
+use std::io::Read;
+use std::path::PathBuf;
+use std::time::Duration;
+use tracing::debug;
+use tracing::warn;
+
+/// Answers from earlier runs, kept at `<dir>/<day>/results/` next to the
+/// fetched inputs. Each part's answer has its own file named after the part,
+/// the SHA-256 of the input and the solver's version, so editing the input or
+/// bumping [`aoc_common::Solution::VERSION`] just stops old entries matching.
+#[derive(Debug, Clone)]
+pub struct ResultCache {
+    dir: PathBuf,
+}
+
+impl ResultCache {
+    pub fn new(dir: PathBuf) -> Self {
+        Self { dir }
+    }
+
+    fn results_dir(&self, day: &Day) -> PathBuf {
+        self.dir.join(day.name).join("results")
+    }
+
+    fn path(&self, day: &Day, part: u8, hash: &str) -> PathBuf {
+        // Versions are free text, keep them to something safe in a file name
+        let version = day
+            .version
+            .chars()
+            .map(|c| match c {
+                'a'..='z' | 'A'..='Z' | '0'..='9' | '.' | '-' => c,
+                _ => '_',
+            })
+            .collect::<String>();
+        self.results_dir(day)
+            .join(format!("part{part}-{hash}-v{version}.json"))
+    }
+
+    /// The answer recorded for `part` of `day` on the input hashing to `hash`.
+    /// Entries that can't be read are treated as missing.
+    pub fn get(&self, day: &Day, part: u8, hash: &str) -> Option<Answer> {
+        let path = self.path(day, part, hash);
+        let raw = fs::read_to_string(&path).ok()?;
+        match serde_json::from_str(&raw) {
+            Ok(answer) => Some(answer),
+            Err(err) => {
+                debug!(path = %path.display(), %err, "ignoring unreadable cached answer");
+                None
+            }
+        }
+    }
+
+    /// Records `answer`. Like fetched inputs it's written aside first, so a
+    /// run that's cut short never leaves half an answer behind.
+    pub fn put(&self, day: &Day, part: u8, hash: &str, answer: &Answer) -> Result<()> {
+        let path = self.path(day, part, hash);
+        let parent = path.parent().expect("result paths have a directory");
+        fs::create_dir_all(parent).with_context(|| format!("creating {}", parent.display()))?;
+        let partial = path.with_extension("partial");
+        fs::write(&partial, serde_json::to_string(answer)?)
+            .with_context(|| format!("writing {}", partial.display()))?;
+        fs::rename(&partial, &path).with_context(|| format!("writing {}", path.display()))?;
+        debug!(day = day.number, part, path = %path.display(), "cached answer");
+        Ok(())
+    }
+
+    /// Solves the parts of `raw` that `part` asks for, reading back the ones
+    /// already cached and recording the rest.
+    pub fn solve(&self, day: &Day, raw: &str, part: Part) -> Result<Report, ParseError> {
+        let hash = hash(raw.as_bytes()).expect("reading from memory");
+        self.solve_hashed(day, &hash, part, |part| (day.solve)(raw, part))
+    }
+
+    /// Like [`Self::solve`], but with `stream` it streams `source` for days
+    /// that can. A file is then hashed in a first pass so that memory stays
+    /// flat; stdin can't be read twice, so streaming it skips the cache.
+    pub fn solve_source(
+        &self,
+        day: &Day,
+        source: &InputSource,
+        part: Part,
+        stream: bool,
+    ) -> Result<Report> {
+        match (day.stream.filter(|_| stream), source) {
+            (Some(_), InputSource::Stdin) => day.solve_source(source, part, true),
+            (Some(_), InputSource::Path(_)) => {
+                let hash = hash(source.open()?).with_context(|| format!("reading {source}"))?;
+                self.solve_hashed(day, &hash, part, |part| {
+                    day.solve_source(source, part, true)
+                })
+            }
+            _ => {
+                let raw = source.read()?;
+                Ok(self
+                    .solve(day, &raw, part)
+                    .map_err(|err| err.with_file(source))?)
+            }
+        }
+    }
+
+    /// When every part is cached the input isn't even parsed.
+    fn solve_hashed<E>(
+        &self,
+        day: &Day,
+        hash: &str,
+        part: Part,
+        solve: impl FnOnce(Part) -> Result<Report, E>,
+    ) -> Result<Report, E> {
+        let mut cached = Vec::new();
+        let mut missing = Vec::new();
+        for n in [1, 2].into_iter().filter(|&n| part.includes(n)) {
+            match self.get(day, n, hash) {
+                Some(answer) => {
+                    debug!(day = day.number, part = n, %answer, "using cached answer");
+                    cached.push(PartReport {
+                        part: n,
+                        answer,
+                        time: Duration::ZERO,
+                        cached: true,
+                    });
+                }
+                None => missing.push(n),
+            }
+        }
+        let mut report = match missing.as_slice() {
+            [] => Report {
+                parse: Duration::ZERO,
+                parts: Vec::new(),
+            },
+            [1] => solve(Part::One)?,
+            [2] => solve(Part::Two)?,
+            _ => solve(Part::Both)?,
+        };
+        for solved in &report.parts {
+            // Failing to cache only costs the next run some time
+            if let Err(err) = self.put(day, solved.part, hash, &solved.answer) {
+                warn!(day = day.number, part = solved.part, "{err:#}");
+            }
+        }
+        report.parts.extend(cached);
+        report.parts.sort_by_key(|solved| solved.part);
+        Ok(report)
+    }
+
+    /// Forgets the cached answers for `day`, or for every day, leaving
+    /// fetched inputs alone. Returns how many answers were removed.
+    pub fn clear(&self, day: Option<&Day>) -> Result<usize> {
+        let dirs = match day {
+            Some(day) => vec![self.results_dir(day)],
+            None => match fs::read_dir(&self.dir) {
+                Ok(entries) => entries
+                    .map(|entry| Ok(entry?.path().join("results")))
+                    .collect::<io::Result<Vec<_>>>()
+                    .with_context(|| format!("reading {}", self.dir.display()))?,
+                Err(err) if err.kind() == io::ErrorKind::NotFound => Vec::new(),
+                Err(err) => {
+                    return Err(err).with_context(|| format!("reading {}", self.dir.display()))
+                }
+            },
+        };
+        let mut removed = 0;
+        for dir in dirs.into_iter().filter(|dir| dir.is_dir()) {
+            removed += fs::read_dir(&dir)
+                .with_context(|| format!("reading {}", dir.display()))?
+                .count();
+            fs::remove_dir_all(&dir).with_context(|| format!("removing {}", dir.display()))?;
+        }
+        Ok(removed)
+    }
+}
+
+/// The input's SHA-256 in hex.
+fn hash(mut input: impl Read) -> io::Result<String> {
+    let mut hasher = Sha256::new();
+    io::copy(&mut input, &mut hasher)?;
+    Ok(hasher
+        .finalize()
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn answers_are_reused_until_the_input_changes() {
+        let dir = tempfile::tempdir().unwrap();
+        let results = ResultCache::new(dir.path().to_path_buf());
+        let day = &one::DAY;
+        let input = "1\n2\n\n3\n";
+
+        let first = results.solve(day, input, Part::Both).unwrap();
+        assert!(first.parts.iter().all(|part| !part.cached));
+        let again = results.solve(day, input, Part::Both).unwrap();
+        assert!(again.parts.iter().all(|part| part.cached));
+        let answers = |report: &Report| {
+            report
+                .parts
+                .iter()
+                .map(|part| part.answer.clone())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(answers(&first), answers(&again));
+
+        let changed = results.solve(day, "1\n2\n\n4\n", Part::One).unwrap();
+        assert!(!changed.parts[0].cached);
+        assert_eq!(results.clear(None).unwrap(), 3);
+        assert!(!results.solve(day, input, Part::Two).unwrap().parts[0].cached);
+    }
+
+    #[test]
+    fn solver_version_is_part_of_the_key() {
+        let dir = tempfile::tempdir().unwrap();
+        let results = ResultCache::new(dir.path().to_path_buf());
+        let old = one::DAY;
+        let new = Day {
+            version: "2",
+            ..old
+        };
+        results.put(&old, 1, "abc", &Answer::Integer(1)).unwrap();
+        assert_eq!(results.get(&old, 1, "abc"), Some(Answer::Integer(1)));
+        assert_eq!(results.get(&new, 1, "abc"), None);
+        assert_eq!(results.get(&old, 2, "abc"), None);
+    }
+}
@@ -0,0 +1,424 @@
+use crate::results::ResultCache;
+use anyhow::bail;
+use anyhow::Result;
+use aoc_common::Answer;
+use aoc_common::Day;
+use aoc_common::Format;
+use aoc_common::InputSource;
+use aoc_common::Part;
+use aoc_common::Report;
+use aoc_common::RunOpts;
+use rayon::prelude::*;
+use serde::Serialize;
+use std::any::Any;
+use std::fs;
+use std::panic;
+use std::panic::AssertUnwindSafe;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
+use std::sync::Once;
+use std::time::Duration;
+use std::time::Instant;
+use tracing::info_span;
+use tracing::warn;
+
+/// How one day's answers compare with the ones recorded for its input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Check {
+    Pass,
+    /// The parts that came out differently.
+    Fail(Vec<u8>),
+    /// Nothing is recorded for this input.
+    Unknown,
+}
+
+#[derive(Debug)]
+pub enum Outcome {
+    Solved(Report, Check),
+    Failed(String),
+    Panicked(String),
+}
+
+#[derive(Debug)]
+pub struct DayRun {
+    pub day: &'static Day,
+    pub input: InputSource,
+    pub outcome: Outcome,
+}
+
+impl DayRun {
+    pub fn ok(&self) -> bool {
+        matches!(
+            self.outcome,
+            Outcome::Solved(_, Check::Pass | Check::Unknown)
+        )
+    }
+
+    pub fn status(&self) -> &'static str {
+        match self.outcome {
+            Outcome::Solved(_, Check::Pass) => "pass",
+            Outcome::Solved(_, Check::Fail(_)) => "fail",
+            Outcome::Solved(_, Check::Unknown) => "unknown",
+            Outcome::Failed(_) => "error",
+            Outcome::Panicked(_) => "panic",
+        }
+    }
+}
+
+/// Runs every day at once on the thread pool, each on its cached or
+/// checked-in input, then prints how they all went. A day that errors or
+/// panics is reported as failed without stopping the rest.
+pub fn run_all(
+    days: &'static [Day],
+    input_for: impl Fn(&Day) -> InputSource + Sync,
+    results: Option<&ResultCache>,
+    opts: &RunOpts,
+) -> Result<()> {
+    let start = Instant::now();
+    let runs = run_days(days, input_for, results, opts.part);
+    let wall = start.elapsed();
+
+    match opts.format {
+        Format::Text => print_table(&runs, wall),
+        Format::Json => {
+            for run in &runs {
+                println!("{}", serde_json::to_string(&JsonDay::new(run))?);
+            }
+        }
+    }
+    let failed = runs.iter().filter(|run| !run.ok()).count();
+    if failed > 0 {
+        bail!("{failed} of {} days failed", runs.len());
+    }
+    Ok(())
+}
+
+/// Solves `days` in parallel without printing anything.
+pub fn run_days(
+    days: &'static [Day],
+    input_for: impl Fn(&Day) -> InputSource + Sync,
+    results: Option<&ResultCache>,
+    part: Part,
+) -> Vec<DayRun> {
+    let _quiet = QuietPanics::new();
+    days.par_iter()
+        .map(|day| run_day(day, input_for(day), results, part))
+        .collect()
+}
+
+/// How many [`QuietPanics`] are alive.
+static QUIET: AtomicUsize = AtomicUsize::new(0);
+
+/// Keeps panics on rayon's worker threads from being printed while it's
+/// alive, since [`run_day`] reports them with the rest of the outcome.
+/// Panics on any other thread still reach the hook that was there before.
+///
+/// The filtering hook is installed once and left in place, so dropping the
+/// guard while unwinding has no hook to swap back.
+struct QuietPanics;
+
+impl QuietPanics {
+    fn new() -> Self {
+        static INSTALL: Once = Once::new();
+        INSTALL.call_once(|| {
+            let previous = panic::take_hook();
+            panic::set_hook(Box::new(move |info| {
+                let quiet =
+                    QUIET.load(Ordering::SeqCst) > 0 && rayon::current_thread_index().is_some();
+                if !quiet {
+                    previous(info);
+                }
+            }));
+        });
+        QUIET.fetch_add(1, Ordering::SeqCst);
+        QuietPanics
+    }
+}
+
+impl Drop for QuietPanics {
+    fn drop(&mut self) {
+        QUIET.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+pub fn run_day(
+    day: &'static Day,
+    input: InputSource,
+    results: Option<&ResultCache>,
+    part: Part,
+) -> DayRun {
+    let _span = info_span!("day", day = day.number).entered();
+    let outcome = match panic::catch_unwind(AssertUnwindSafe(|| solve(day, &input, results, part)))
+    {
+        Ok(Ok((report, check))) => Outcome::Solved(report, check),
+        Ok(Err(err)) => Outcome::Failed(format!("{err:#}")),
+        Err(payload) => Outcome::Panicked(panic_message(payload.as_ref())),
+    };
+    if let Outcome::Failed(err) | Outcome::Panicked(err) = &outcome {
+        warn!(%err, "day failed");
+    }
+    DayRun {
+        day,
+        input,
+        outcome,
+    }
+}
+
+fn solve(
+    day: &Day,
+    input: &InputSource,
+    results: Option<&ResultCache>,
+    part: Part,
+) -> Result<(Report, Check)> {
+    let raw = input.read()?;
+    let report = match results {
+        Some(results) => results.solve(day, &raw, part),
+        None => (day.solve)(&raw, part),
+    }
+    .map_err(|err| err.with_file(input))?;
+    // Matched on content, so a fetched copy of a checked-in input counts too
+    let expected = day
+        .expected()?
+        .into_iter()
+        .find(|file| fs::read_to_string(&file.input).is_ok_and(|known| known == raw));
+    let check = match expected {
+        None => Check::Unknown,
+        Some(file) => {
+            let wrong = report
+                .parts
+                .iter()
+                .filter(|got| file.part(got.part).is_some_and(|want| *want != got.answer))
+                .map(|got| got.part)
+                .collect::<Vec<_>>();
+            if wrong.is_empty() {
+                Check::Pass
+            } else {
+                Check::Fail(wrong)
+            }
+        }
+    };
+    Ok((report, check))
+}
+
+fn panic_message(payload: &(dyn Any + Send)) -> String {
+    match (
+        payload.downcast_ref::<&str>(),
+        payload.downcast_ref::<String>(),
+    ) {
+        (Some(msg), _) => msg.to_string(),
+        (_, Some(msg)) => msg.clone(),
+        _ => "unknown panic".to_string(),
+    }
+}
+
+/// One line per answer keeps the table readable; pictures only say how big
+/// they are.
+pub fn short_answer(answer: &Answer) -> String {
+    match answer {
+        Answer::Rows(rows) => format!("({} rows)", rows.len()),
+        other => other.to_string(),
+    }
+}
+
+fn print_table(runs: &[DayRun], wall: Duration) {
+    let header = ["Day", "Name", "Part 1", "Part 2", "Parse", "Solve", "Check"].map(String::from);
+    let mut rows = vec![header.to_vec()];
+    for run in runs {
+        let mut row = vec![run.day.number.to_string(), run.day.name.to_string()];
+        match &run.outcome {
+            Outcome::Solved(report, check) => {
+                for part in [1, 2] {
+                    row.push(
+                        report
+                            .parts
+                            .iter()
+                            .find(|got| got.part == part)
+                            .map_or("-".to_string(), |got| short_answer(&got.answer)),
+                    );
+                }
+                if report.parts.iter().all(|part| part.cached) {
+                    row.extend(["cached", "cached"].map(String::from));
+                } else {
+                    let solve = report.parts.iter().map(|part| part.time).sum::<Duration>();
+                    row.push(format!("{:.1?}", report.parse));
+                    row.push(format!("{solve:.1?}"));
+                }
+                row.push(match check {
+                    Check::Pass => "pass".to_string(),
+                    Check::Fail(parts) => format!(
+                        "FAIL ({})",
+                        parts
+                            .iter()
+                            .map(|part| format!("part {part}"))
+                            .collect::<Vec<_>>()
+                            .join(", ")
+                    ),
+                    Check::Unknown => "-".to_string(),
+                });
+            }
+            Outcome::Failed(_) | Outcome::Panicked(_) => {
+                row.extend(["-", "-", "-", "-"].map(String::from));
+                row.push(run.status().to_uppercase());
+            }
+        }
+        rows.push(row);
+    }
+    let widths = (0..rows[0].len())
+        .map(|col| {
+            rows.iter()
+                .map(|row| row[col].chars().count())
+                .max()
+                .unwrap_or(0)
+        })
+        .collect::<Vec<_>>();
+    for row in &rows {
+        let line = row
+            .iter()
+            .zip(&widths)
+            .enumerate()
+            .map(|(col, (cell, &width))| {
+                // Numbers line up on the right
+                if col == 0 {
+                    format!("{cell:>width$}")
+                } else {
+                    format!("{cell:<width$}")
+                }
+            })
+            .collect::<Vec<_>>()
+            .join("  ");
+        println!("{}", line.trim_end());
+    }
+
+    for run in runs {
+        if let Outcome::Failed(err) | Outcome::Panicked(err) = &run.outcome {
+            println!();
+            println!(
+                "Day {} ({}) {}: {err}",
+                run.day.number,
+                run.input,
+                run.status()
+            );
+        }
+    }
+    let failed = runs.iter().filter(|run| !run.ok()).count();
+    println!();
+    match failed {
+        0 => println!("{} days in {wall:.1?}, none failed", runs.len()),
+        _ => println!("{} days in {wall:.1?}, {failed} failed", runs.len()),
+    }
+}
+
+#[derive(Serialize)]
+struct JsonDay<'a> {
+    day: u32,
+    name: &'a str,
+    input: String,
+    check: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<&'a str>,
+    #[serde(flatten)]
+    report: Option<&'a Report>,
+}
+
+impl<'a> JsonDay<'a> {
+    fn new(run: &'a DayRun) -> Self {
+        let (report, error) = match &run.outcome {
+            Outcome::Solved(report, _) => (Some(report), None),
+            Outcome::Failed(err) | Outcome::Panicked(err) => (None, Some(err.as_str())),
+        };
+        Self {
+            day: run.day.number,
+            name: run.day.name,
+            input: run.input.to_string(),
+            check: run.status(),
+            error,
+            report,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::Generator;
+    use aoc_common::ParseError;
+    use aoc_common::Rng;
+    use aoc_common::Solution;
+    use std::io;
+    use std::io::Write;
+
+    /// Panics in its parser on anything but `ok`.
+    struct Fragile;
+
+    impl Solution for Fragile {
+        type Input = u64;
+        type Output1 = u64;
+        type Output2 = u64;
+
+        fn parse(input: &str) -> Result<u64, ParseError> {
+            assert_eq!(input, "ok", "fragile parser");
+            Ok(1)
+        }
+
+        fn part1(input: &u64) -> u64 {
+            *input
+        }
+
+        fn part2(input: &u64) -> u64 {
+            *input + 1
+        }
+    }
+
+    impl Generator for Fragile {
+        const UNIT: &'static str = "nothing";
+
+        fn generate(_: &mut Rng, _: usize, _: &mut dyn Write) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    static FRAGILE: Day = Day::new::<Fragile>(99, "fragile");
+
+    #[test]
+    fn panics_are_caught_per_day() {
+        let run = run_day(
+            &FRAGILE,
+            InputSource::Inline("boom".into()),
+            None,
+            Part::Both,
+        );
+        assert!(!run.ok());
+        match run.outcome {
+            Outcome::Panicked(msg) => assert!(msg.contains("fragile parser"), "{msg}"),
+            other => panic!("expected a panic, got {other:?}"),
+        }
+
+        let run = run_day(&FRAGILE, InputSource::Inline("ok".into()), None, Part::Both);
+        assert!(run.ok());
+        assert_eq!(run.status(), "unknown");
+    }
+
+    #[test]
+    fn parallel_runs_catch_panics_too() {
+        let runs = run_days(
+            std::slice::from_ref(&FRAGILE),
+            |_| InputSource::Inline("boom".into()),
+            None,
+            Part::Both,
+        );
+        assert!(matches!(&runs[0].outcome, Outcome::Panicked(_)));
+        // Dropping the guard while unwinding mustn't abort
+        let caught = panic::catch_unwind(|| {
+            let _quiet = QuietPanics::new();
+            panic!("unwinding past the guard");
+        });
+        assert!(caught.is_err());
+    }
+
+    #[test]
+    fn pictures_are_summarised() {
+        let screen = Answer::Rows(vec!["#.".into(), ".#".into()]);
+        assert_eq!(short_answer(&screen), "(2 rows)");
+        assert_eq!(short_answer(&Answer::Integer(7)), "7");
+    }
+}
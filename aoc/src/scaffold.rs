@@ -0,0 +1,111 @@
+use anyhow::anyhow;
+use anyhow::bail;
+use anyhow::Context;
+use anyhow::Result;
+use std::fs;
+use std::path::Path;
+
+const NAMES: [&str; 25] = [
+    "one",
+    "two",
+    "three",
+    "four",
+    "five",
+    "six",
+    "seven",
+    "eight",
+    "nine",
+    "ten",
+    "eleven",
+    "twelve",
+    "thirteen",
+    "fourteen",
+    "fifteen",
+    "sixteen",
+    "seventeen",
+    "eighteen",
+    "nineteen",
+    "twenty",
+    "twentyone",
+    "twentytwo",
+    "twentythree",
+    "twentyfour",
+    "twentyfive",
+];
+
+const TEMPLATES: [(&str, &str); 3] = [
+    ("Cargo.toml", include_str!("../templates/Cargo.toml.tmpl")),
+    ("src/main.rs", include_str!("../templates/main.rs.tmpl")),
+    ("src/lib.rs", include_str!("../templates/lib.rs.tmpl")),
+];
+
+/// Creates the crate for day `number` under the workspace at `root` and
+/// registers it with the workspace, the `aoc` crate and its registry.
+pub fn new_day(root: &Path, number: u32) -> Result<()> {
+    let name = number
+        .checked_sub(1)
+        .and_then(|index| NAMES.get(index as usize))
+        .ok_or_else(|| anyhow!("days run from 1 to 25, not {number}"))?;
+    let dir = root.join(name);
+    if dir.exists() {
+        bail!("{} already exists", dir.display());
+    }
+    let manifest = root.join("Cargo.toml");
+    let aoc_manifest = root.join("aoc/Cargo.toml");
+    let registry = root.join("aoc/src/registry.rs");
+    // Work out every edit before touching anything, so a tree we don't
+    // recognise is left alone
+    let manifest_text = insert_before(
+        &read(&manifest)?,
+        "    \"aoc\",\n",
+        &format!("    \"{name}\",\n"),
+    )
+    .ok_or_else(|| anyhow!("{}: no `aoc` workspace member", manifest.display()))?;
+    let aoc_text = insert_after_last_day(&read(&aoc_manifest)?, name)
+        .ok_or_else(|| anyhow!("{}: no day dependencies", aoc_manifest.display()))?;
+    let registry_text = insert_before(&read(&registry)?, "];\n", &format!("    {name}::DAY,\n"))
+        .ok_or_else(|| anyhow!("{}: no DAYS list", registry.display()))?;
+
+    for (path, template) in TEMPLATES {
+        let path = dir.join(path);
+        fs::create_dir_all(path.parent().unwrap())?;
+        fs::write(&path, fill(template, name, number))
+            .with_context(|| format!("writing {}", path.display()))?;
+    }
+    write(&manifest, &manifest_text)?;
+    write(&aoc_manifest, &aoc_text)?;
+    write(&registry, &registry_text)?;
+    Ok(())
+}
+
+fn fill(template: &str, name: &str, number: u32) -> String {
+    let mut type_name = name.to_string();
+    type_name[..1].make_ascii_uppercase();
+    template
+        .replace("{{name}}", name)
+        .replace("{{Name}}", &type_name)
+        .replace("{{number}}", &number.to_string())
+}
+
+fn insert_before(text: &str, marker: &str, line: &str) -> Option<String> {
+    let pos = text.find(marker)?;
+    Some(format!("{}{line}{}", &text[..pos], &text[pos..]))
+}
+
+/// Adds a path dependency on `name` after the last day's.
+fn insert_after_last_day(text: &str, name: &str) -> Option<String> {
+    let line = text
+        .lines()
+        .rfind(|line| line.contains("path = \"../") && !line.starts_with("aoc-common"))?;
+    let end = text.find(line)? + line.len() + 1;
+    let dep = format!("{name} = {{ path = \"../{name}\" }}\n");
+    Some(format!("{}{dep}{}", &text[..end], &text[end..]))
+}
+
+fn read(path: &Path) -> Result<String> {
+    fs::read_to_string(path).with_context(|| format!("reading {}", path.display()))
+}
+
+fn write(path: &Path, text: &str) -> Result<()> {
+    fs::write(path, text).with_context(|| format!("writing {}", path.display()))
+}
@@ -0,0 +1,511 @@
+use crate::results::ResultCache;
+use crate::run_all::run_days;
+use crate::run_all::short_answer;
+use crate::run_all::Check;
+use crate::run_all::DayRun;
+use crate::run_all::Outcome;
+use anyhow::anyhow;
+use anyhow::Result;
+use aoc_common::in_file;
+use aoc_common::write_png;
+use aoc_common::Answer;
+use aoc_common::Day;
+use aoc_common::InputSource;
+use aoc_common::Node;
+use aoc_common::Part;
+use clap::Args;
+use std::collections::HashMap;
+use std::fmt::Write as _;
+use std::io::Cursor;
+use std::panic;
+use std::panic::AssertUnwindSafe;
+use std::slice;
+use std::time::Duration;
+use tiny_http::Header;
+use tiny_http::Method;
+use tiny_http::Response;
+use tiny_http::Server;
+use tracing::info;
+use tracing::info_span;
+use tracing::warn;
+
+#[derive(Debug, Args)]
+pub struct ServeOpts {
+    #[clap(
+        long,
+        default_value_t = 8022,
+        help = "Port to listen on, 0 for any free one"
+    )]
+    port: u16,
+}
+
+/// Largest a picture is shown, in pixels across; small grids are scaled up
+/// to about this.
+const DISPLAY_WIDTH: usize = 640;
+
+/// A view's frames as PNGs, one pixel per cell, so scrubbing back and forth
+/// doesn't draw them again. The browser does the scaling.
+struct Frames {
+    /// The input they were drawn from, in case it has changed since.
+    raw: String,
+    width: usize,
+    height: usize,
+    pngs: Vec<Vec<u8>>,
+}
+
+/// What a request gets back.
+enum Page {
+    Html(String),
+    Png(Vec<u8>),
+    NotFound,
+    /// Everything is read only.
+    NotAllowed,
+}
+
+struct Dashboard<'a, F> {
+    days: &'static [Day],
+    input_for: F,
+    results: &'a ResultCache,
+    frames: HashMap<(u32, &'static str), Frames>,
+}
+
+/// Serves a page per day on 127.0.0.1 until killed, solving and drawing
+/// everything the same way `run` and `render` do. Pages are built on
+/// request, so editing an input and reloading shows the change.
+pub fn serve(
+    days: &'static [Day],
+    input_for: impl Fn(&Day) -> InputSource + Sync,
+    results: &ResultCache,
+    opts: &ServeOpts,
+) -> Result<()> {
+    let server = Server::http(("127.0.0.1", opts.port))
+        .map_err(|err| anyhow!("listening on port {}: {err}", opts.port))?;
+    println!("Serving on http://{}/", server.server_addr());
+    let mut dashboard = Dashboard {
+        days,
+        input_for,
+        results,
+        frames: HashMap::new(),
+    };
+    for request in server.incoming_requests() {
+        let url = request.url().to_string();
+        let _span = info_span!("request", %url).entered();
+        let page = if *request.method() == Method::Get {
+            // A day that panics while drawing shouldn't take the server down
+            panic::catch_unwind(AssertUnwindSafe(|| dashboard.page(&url)))
+                .unwrap_or_else(|_| Err(anyhow!("panicked, see the server's output")))
+        } else {
+            Ok(Page::NotAllowed)
+        };
+        let response = match page {
+            Ok(Page::Html(html)) => respond(200, "text/html; charset=utf-8", html.into_bytes()),
+            Ok(Page::Png(png)) => respond(200, "image/png", png),
+            Ok(Page::NotFound) => respond(404, "text/plain", b"not found".to_vec()),
+            Ok(Page::NotAllowed) => respond(405, "text/plain", b"only GET is served".to_vec()),
+            Err(err) => {
+                warn!("{err:#}");
+                respond(500, "text/plain", format!("{err:#}").into_bytes())
+            }
+        };
+        info!(status = response.status_code().0, "served");
+        if let Err(err) = request.respond(response) {
+            warn!(%err, "writing response");
+        }
+    }
+    Ok(())
+}
+
+fn respond(status: u16, content_type: &str, body: Vec<u8>) -> Response<Cursor<Vec<u8>>> {
+    let header = Header::from_bytes("Content-Type", content_type).expect("a valid header");
+    Response::from_data(body)
+        .with_status_code(status)
+        .with_header(header)
+}
+
+impl<F: Fn(&Day) -> InputSource + Sync> Dashboard<'_, F> {
+    fn page(&mut self, url: &str) -> Result<Page> {
+        let path = url.split(['?', '#']).next().unwrap_or_default();
+        let parts = path.trim_matches('/').split('/').collect::<Vec<_>>();
+        Ok(match parts.as_slice() {
+            [""] => Page::Html(self.index()),
+            ["day", day] => match self.find(day) {
+                Some(day) => Page::Html(self.day(day)),
+                None => Page::NotFound,
+            },
+            ["day", day, view, frame] => {
+                let frame = frame
+                    .strip_suffix(".png")
+                    .and_then(|n| n.parse::<usize>().ok());
+                match (self.find(day), frame) {
+                    (Some(day), Some(frame)) => match day.views.iter().find(|v| *v == view) {
+                        Some(view) => self
+                            .frames(day, view)?
+                            .pngs
+                            .get(frame)
+                            .map_or(Page::NotFound, |png| Page::Png(png.clone())),
+                        None => Page::NotFound,
+                    },
+                    _ => Page::NotFound,
+                }
+            }
+            _ => Page::NotFound,
+        })
+    }
+
+    fn find(&self, day: &str) -> Option<&'static Day> {
+        self.days
+            .iter()
+            .find(|d| d.number.to_string() == day || d.name == day)
+    }
+
+    fn index(&self) -> String {
+        let runs = run_days(self.days, &self.input_for, Some(self.results), Part::Both);
+        let mut html = String::from(
+            "<table><tr><th>Day</th><th>Name</th><th>Part 1</th><th>Part 2</th>\
+             <th>Parse</th><th>Solve</th><th>Check</th></tr>",
+        );
+        for run in &runs {
+            let day = run.day;
+            let _ = write!(
+                html,
+                "<tr><td class=num>{}</td><td><a href=\"/day/{}\">{}</a></td>",
+                day.number, day.number, day.name
+            );
+            match &run.outcome {
+                Outcome::Solved(report, _) => {
+                    for part in [1, 2] {
+                        let answer = report
+                            .parts
+                            .iter()
+                            .find(|got| got.part == part)
+                            .map_or("-".to_string(), |got| short_answer(&got.answer));
+                        let _ = write!(html, "<td><code>{}</code></td>", escape(&answer));
+                    }
+                    let (parse, solve) = timings(run);
+                    let _ = write!(html, "<td class=num>{parse}</td><td class=num>{solve}</td>");
+                }
+                Outcome::Failed(_) | Outcome::Panicked(_) => {
+                    html.push_str(&"<td>-</td>".repeat(4));
+                }
+            }
+            let _ = write!(html, "<td>{}</td></tr>", check(run));
+        }
+        html.push_str("</table>");
+        let failed = runs.iter().filter(|run| !run.ok()).count();
+        let _ = write!(
+            html,
+            "<p>{} days, {}</p>",
+            runs.len(),
+            match failed {
+                0 => "none failed".to_string(),
+                _ => format!("{failed} failed"),
+            }
+        );
+        document("Advent of Code 2022", &html)
+    }
+
+    fn day(&mut self, day: &'static Day) -> String {
+        let source = (self.input_for)(day);
+        // Run on the pool so a panic is reported on the page, not printed
+        let run = run_days(
+            slice::from_ref(day),
+            |_| source.clone(),
+            Some(self.results),
+            Part::Both,
+        )
+        .remove(0);
+        let mut html = format!(
+            "<p><a href=\"/\">All days</a> &middot; input <code>{}</code> &middot; {}</p>",
+            escape(&source.to_string()),
+            check(&run)
+        );
+        match &run.outcome {
+            Outcome::Solved(report, _) => {
+                html.push_str("<h2>Answers</h2><table>");
+                for part in &report.parts {
+                    let time = match part.cached {
+                        true => "cached".to_string(),
+                        false => format!("{:.1?}", part.time),
+                    };
+                    let answer = match &part.answer {
+                        Answer::Rows(_) => {
+                            format!("<pre>{}</pre>", escape(&part.answer.to_string()))
+                        }
+                        answer => format!("<code>{}</code>", escape(&answer.to_string())),
+                    };
+                    let _ = write!(
+                        html,
+                        "<tr><th>Part {}</th><td>{answer}</td><td class=num>{time}</td></tr>",
+                        part.part
+                    );
+                }
+                let (parse, _) = timings(&run);
+                let _ = write!(
+                    html,
+                    "<tr><th>Parse</th><td></td><td class=num>{parse}</td></tr></table>"
+                );
+            }
+            Outcome::Failed(err) | Outcome::Panicked(err) => {
+                let _ = write!(html, "<pre class=error>{}</pre>", escape(err));
+            }
+        }
+
+        for view in day.views {
+            let _ = write!(html, "<h2>{}</h2>", escape(view));
+            match self.frames(day, view) {
+                Ok(frames) => html.push_str(&figure(day, view, frames)),
+                Err(err) => {
+                    let _ = write!(
+                        html,
+                        "<pre class=error>{}</pre>",
+                        escape(&format!("{err:#}"))
+                    );
+                }
+            }
+        }
+
+        if let Some(outline) = day.outline {
+            html.push_str("<h2>Tree</h2>");
+            match source.read() {
+                Ok(raw) => match outline(&raw) {
+                    Ok(root) => {
+                        html.push_str("<ul class=tree>");
+                        tree(&root, 0, &mut html);
+                        html.push_str("</ul>");
+                    }
+                    Err(err) => {
+                        let err = err.with_file(&source).to_string();
+                        let _ = write!(html, "<pre class=error>{}</pre>", escape(&err));
+                    }
+                },
+                Err(err) => {
+                    let _ = write!(
+                        html,
+                        "<pre class=error>{}</pre>",
+                        escape(&format!("{err:#}"))
+                    );
+                }
+            }
+        }
+        document(&format!("Day {}: {}", day.number, day.name), &html)
+    }
+
+    /// Draws every frame of `view`, reusing the last drawing while the input
+    /// stays the same.
+    fn frames(&mut self, day: &'static Day, view: &'static str) -> Result<&Frames> {
+        let draw = day
+            .render
+            .ok_or_else(|| anyhow!("day {} has nothing to render", day.number))?;
+        let source = (self.input_for)(day);
+        let raw = source.read()?;
+        let key = (day.number, view);
+        if self.frames.get(&key).is_none_or(|frames| frames.raw != raw) {
+            let mut frames = Frames {
+                raw: String::new(),
+                width: 0,
+                height: 0,
+                pngs: Vec::new(),
+            };
+            draw(&raw, view, &mut |frame| {
+                (frames.width, frames.height) = (frame.width(), frame.height());
+                let mut png = Vec::new();
+                write_png(frame, 1, &mut png)?;
+                frames.pngs.push(png);
+                Ok(())
+            })
+            .map_err(|err| in_file(err, &source))?;
+            info!(
+                day = day.number,
+                view,
+                frames = frames.pngs.len(),
+                "drew view"
+            );
+            frames.raw = raw;
+            self.frames.insert(key, frames);
+        }
+        Ok(&self.frames[&key])
+    }
+}
+
+fn timings(run: &DayRun) -> (String, String) {
+    match &run.outcome {
+        Outcome::Solved(report, _) if report.parts.iter().all(|part| part.cached) => {
+            ("cached".to_string(), "cached".to_string())
+        }
+        Outcome::Solved(report, _) => {
+            let solve = report.parts.iter().map(|part| part.time).sum::<Duration>();
+            (format!("{:.1?}", report.parse), format!("{solve:.1?}"))
+        }
+        _ => ("-".to_string(), "-".to_string()),
+    }
+}
+
+fn check(run: &DayRun) -> String {
+    match &run.outcome {
+        Outcome::Solved(_, Check::Pass) => "<span class=pass>pass</span>".to_string(),
+        Outcome::Solved(_, Check::Fail(parts)) => format!(
+            "<span class=error>FAIL ({})</span>",
+            parts
+                .iter()
+                .map(|part| format!("part {part}"))
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+        Outcome::Solved(_, Check::Unknown) => "no recorded answers".to_string(),
+        Outcome::Failed(_) | Outcome::Panicked(_) => {
+            format!("<span class=error>{}</span>", run.status())
+        }
+    }
+}
+
+/// A view's last frame, with a slider and play button to move through the
+/// rest when there are more.
+fn figure(day: &Day, view: &str, frames: &Frames) -> String {
+    let Some(last) = frames.pngs.len().checked_sub(1) else {
+        return "<p>Nothing to draw.</p>".to_string();
+    };
+    let scale = (DISPLAY_WIDTH / frames.width.max(1)).clamp(1, 16);
+    let src = format!("/day/{}/{}/", day.number, escape(view));
+    let img = format!(
+        "<img src=\"{src}{last}.png\" width={} height={} alt=\"{}\">",
+        frames.width * scale,
+        frames.height * scale,
+        escape(view)
+    );
+    if last == 0 {
+        return format!("<figure>{img}</figure>");
+    }
+    format!(
+        "<figure class=scrub data-src=\"{src}\">{img}<figcaption>\
+         <button type=button>Play</button> \
+         <input type=range min=0 max={last} value={last}> \
+         <span>frame {} of {}</span></figcaption></figure>",
+        last + 1,
+        last + 1
+    )
+}
+
+/// Lists `node` and everything under it, with the top levels open.
+fn tree(node: &Node, depth: usize, html: &mut String) {
+    let label = escape(&node.label);
+    if node.children.is_empty() {
+        let _ = write!(html, "<li>{label}</li>");
+        return;
+    }
+    let open = if depth < 2 { " open" } else { "" };
+    let _ = write!(html, "<li><details{open}><summary>{label}</summary><ul>");
+    for child in &node.children {
+        tree(child, depth + 1, html);
+    }
+    html.push_str("</ul></details></li>");
+}
+
+fn escape(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+/// A whole page; the styles and the scrubbing script are inline so nothing
+/// is fetched from anywhere else.
+fn document(title: &str, body: &str) -> String {
+    format!(
+        r#"<!DOCTYPE html>
+<html lang="en">
+<head>
+<meta charset="utf-8">
+<title>{title}</title>
+<style>
+body {{ font-family: sans-serif; margin: 2em auto; max-width: 60em; background: #0f0f23; color: #ccc; }}
+a {{ color: #090; }}
+h1, h2 {{ color: #fff; font-weight: normal; }}
+table {{ border-collapse: collapse; }}
+th, td {{ padding: 0.2em 0.8em; text-align: left; vertical-align: top; }}
+td.num {{ text-align: right; }}
+code, pre {{ color: #fff; }}
+img {{ image-rendering: pixelated; background: #000; }}
+input[type=range] {{ width: 30em; vertical-align: middle; }}
+.pass {{ color: #090; }}
+.error {{ color: #f55; }}
+.tree, .tree ul {{ list-style: none; padding-left: 1.2em; }}
+</style>
+</head>
+<body>
+<h1>{title}</h1>
+{body}
+<script>
+for (const figure of document.querySelectorAll(".scrub")) {{
+  const img = figure.querySelector("img");
+  const range = figure.querySelector("input");
+  const label = figure.querySelector("span");
+  const button = figure.querySelector("button");
+  const last = Number(range.max);
+  let timer = null;
+  const show = () => {{
+    img.src = figure.dataset.src + range.value + ".png";
+    label.textContent = `frame ${{Number(range.value) + 1}} of ${{last + 1}}`;
+  }};
+  const stop = () => {{
+    clearInterval(timer);
+    timer = null;
+    button.textContent = "Play";
+  }};
+  range.addEventListener("input", () => {{
+    stop();
+    show();
+  }});
+  button.addEventListener("click", () => {{
+    if (timer !== null) {{
+      stop();
+      return;
+    }}
+    if (Number(range.value) === last) {{
+      range.value = 0;
+    }}
+    button.textContent = "Pause";
+    timer = setInterval(() => {{
+      if (Number(range.value) >= last) {{
+        stop();
+        return;
+      }}
+      range.value = Number(range.value) + 1;
+      show();
+    }}, 50);
+  }});
+}}
+</script>
+</body>
+</html>
+"#,
+        title = escape(title),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn trees_nest_and_escape() {
+        let mut root = Node::new("/ (3)");
+        let mut a = Node::new("a (2)");
+        a.children.push(Node::new("<b> (1)"));
+        root.children.push(a);
+        let mut html = String::new();
+        tree(&root, 0, &mut html);
+        assert_eq!(
+            html,
+            "<li><details open><summary>/ (3)</summary><ul>\
+             <li><details open><summary>a (2)</summary><ul>\
+             <li>&lt;b&gt; (1)</li></ul></details></li></ul></details></li>"
+        );
+    }
+}
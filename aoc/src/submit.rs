@@ -0,0 +1,333 @@
+use crate::client::Client;
+use crate::client::ClientOpts;
+use crate::inputs::InputCache;
+use anyhow::anyhow;
+use anyhow::bail;
+use anyhow::Context;
+use anyhow::Result;
+use aoc_common::Answer;
+use aoc_common::Day;
+use aoc_common::InputSource;
+use aoc_common::Part;
+use serde::Deserialize;
+use serde::Serialize;
+use std::fmt;
+use std::fs;
+use std::path::PathBuf;
+use std::time::SystemTime;
+use std::time::UNIX_EPOCH;
+use tracing::debug;
+
+/// Submits `part` of `day`, either `answer` or the one solved from `source`,
+/// unless earlier attempts already rule it out.
+pub fn submit(
+    day: &Day,
+    part: u8,
+    answer: Option<String>,
+    source: &InputSource,
+    cache: &InputCache,
+    client: &ClientOpts,
+) -> Result<()> {
+    let answer = match answer {
+        Some(answer) => answer.trim().to_string(),
+        None => solve(day, part, source)?,
+    };
+    let mut attempts = Attempts::load(cache.day_dir(day).join("attempts.json"))?;
+    attempts.check(part, &answer, now())?;
+    let page = Client::new(client)?.submit(day.number, part, &answer)?;
+    let response = Response::parse(&page)
+        .ok_or_else(|| anyhow!("unrecognised response to {answer}:\n{}", excerpt(&page)))?;
+    debug!(verdict = ?response.verdict, wait_secs = ?response.wait_secs, "site answered");
+    if response.verdict != Verdict::AlreadySolved {
+        attempts.record(Attempt {
+            part,
+            answer: answer.clone(),
+            verdict: response.verdict,
+            at: now(),
+            wait_secs: response.wait_secs,
+        })?;
+    }
+    println!(
+        "Day {} part {part}: {answer} is {}",
+        day.number, response.verdict
+    );
+    match (response.verdict, response.wait_secs) {
+        (Verdict::Right | Verdict::AlreadySolved, _) => Ok(()),
+        (_, Some(wait)) => bail!("{answer} was not accepted, wait {wait}s before the next try"),
+        _ => bail!("{answer} was not accepted"),
+    }
+}
+
+fn solve(day: &Day, part: u8, source: &InputSource) -> Result<String> {
+    let raw = source.read()?;
+    let which = if part == 1 { Part::One } else { Part::Two };
+    let report = (day.solve)(&raw, which).map_err(|err| err.with_file(source))?;
+    match &report.parts[0].answer {
+        Answer::Rows(_) => bail!(
+            "day {} part {part} is a picture, read it off and pass it with --answer",
+            day.number
+        ),
+        answer => Ok(answer.to_string()),
+    }
+}
+
+/// The start of a page's text, for reporting responses we don't understand.
+fn excerpt(page: &str) -> String {
+    let body = page.find("<main>").map_or(page, |start| &page[start..]);
+    body.chars().take(300).collect()
+}
+
+/// What the site said about a submitted answer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Verdict {
+    Right,
+    Wrong,
+    TooHigh,
+    TooLow,
+    /// Submitted too soon after the last answer, so it wasn't checked.
+    Wait,
+    /// The part was already solved on the site.
+    AlreadySolved,
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let val = match self {
+            Verdict::Right => "right",
+            Verdict::Wrong => "wrong",
+            Verdict::TooHigh => "wrong, too high",
+            Verdict::TooLow => "wrong, too low",
+            Verdict::Wait => "not checked, submitted too soon",
+            Verdict::AlreadySolved => "not checked, the part is already solved",
+        };
+        write!(f, "{val}")
+    }
+}
+
+/// A parsed response page: the verdict and how long to wait before the next
+/// attempt, if the site asked for one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Response {
+    pub verdict: Verdict,
+    pub wait_secs: Option<u64>,
+}
+
+impl Response {
+    pub fn parse(page: &str) -> Option<Self> {
+        let text = page.to_lowercase();
+        let verdict = if text.contains("that's the right answer") {
+            Verdict::Right
+        } else if text.contains("you gave an answer too recently") {
+            Verdict::Wait
+        } else if text.contains("don't seem to be solving the right level") {
+            Verdict::AlreadySolved
+        } else if text.contains("that's not the right answer") {
+            if text.contains("your answer is too high") {
+                Verdict::TooHigh
+            } else if text.contains("your answer is too low") {
+                Verdict::TooLow
+            } else {
+                Verdict::Wrong
+            }
+        } else {
+            return None;
+        };
+        Some(Self {
+            verdict,
+            wait_secs: parse_wait(&text),
+        })
+    }
+}
+
+/// Reads "you have 1m 5s left to wait" or "please wait one minute" style
+/// phrases as a number of seconds.
+fn parse_wait(text: &str) -> Option<u64> {
+    if let Some(end) = text.find(" left to wait") {
+        let start = text[..end].rfind("you have ")? + "you have ".len();
+        let mut secs = 0;
+        for part in text[start..end].split_whitespace() {
+            let (num, unit) = part.split_at(part.find(|c: char| !c.is_ascii_digit())?);
+            let num = num.parse::<u64>().ok()?;
+            secs += match unit {
+                "h" => num * 3600,
+                "m" => num * 60,
+                "s" => num,
+                _ => return None,
+            };
+        }
+        return Some(secs);
+    }
+    let start = text.find("please wait ")?;
+    let mut words = text[start + "please wait ".len()..].split_whitespace();
+    let num = match words.next()? {
+        "one" => 1,
+        "two" => 2,
+        "five" => 5,
+        "ten" => 10,
+        other => other.parse::<u64>().ok()?,
+    };
+    match words.next()? {
+        unit if unit.starts_with("minute") => Some(num * 60),
+        unit if unit.starts_with("second") => Some(num),
+        _ => None,
+    }
+}
+
+/// One submitted answer and what came of it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Attempt {
+    pub part: u8,
+    pub answer: String,
+    pub verdict: Verdict,
+    /// Seconds since the Unix epoch.
+    pub at: u64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub wait_secs: Option<u64>,
+}
+
+/// Every answer submitted for a day, kept next to its cached input.
+#[derive(Debug)]
+pub struct Attempts {
+    path: PathBuf,
+    attempts: Vec<Attempt>,
+}
+
+impl Attempts {
+    pub fn load(path: PathBuf) -> Result<Self> {
+        let attempts = match fs::read_to_string(&path) {
+            Ok(raw) => {
+                serde_json::from_str(&raw).with_context(|| format!("parsing {}", path.display()))?
+            }
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => Vec::new(),
+            Err(err) => return Err(err).with_context(|| format!("reading {}", path.display())),
+        };
+        Ok(Self { path, attempts })
+    }
+
+    pub fn record(&mut self, attempt: Attempt) -> Result<()> {
+        self.attempts.push(attempt);
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent).with_context(|| format!("creating {}", parent.display()))?;
+        }
+        let raw = serde_json::to_string_pretty(&self.attempts)?;
+        fs::write(&self.path, raw).with_context(|| format!("writing {}", self.path.display()))
+    }
+
+    /// Refuses answers the site has already ruled out, or any answer while
+    /// the site has asked us to wait.
+    pub fn check(&self, part: u8, answer: &str, now: u64) -> Result<()> {
+        let attempts = self.attempts.iter().filter(|a| a.part == part);
+        if let Some(ready) = attempts.clone().filter_map(ready_at).max() {
+            if ready > now {
+                bail!("the site asked to wait, try again in {}s", ready - now);
+            }
+        }
+        let value = answer.parse::<i128>().ok();
+        for attempt in attempts {
+            let bound = attempt.answer.parse::<i128>().ok();
+            match (attempt.verdict, value, bound) {
+                (Verdict::Right, _, _) if attempt.answer == answer => {
+                    bail!("part {part} was already solved with {answer}")
+                }
+                (Verdict::Right, _, _) => {
+                    bail!("part {part} was already solved with {}", attempt.answer)
+                }
+                (Verdict::Wrong | Verdict::TooHigh | Verdict::TooLow, _, _)
+                    if attempt.answer == answer =>
+                {
+                    bail!("{answer} was already rejected for part {part}")
+                }
+                (Verdict::TooHigh, Some(value), Some(bound)) if value >= bound => {
+                    bail!("{answer} is ruled out, {bound} was already too high")
+                }
+                (Verdict::TooLow, Some(value), Some(bound)) if value <= bound => {
+                    bail!("{answer} is ruled out, {bound} was already too low")
+                }
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+}
+
+fn ready_at(attempt: &Attempt) -> Option<u64> {
+    attempt.wait_secs.map(|wait| attempt.at + wait)
+}
+
+pub fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_secs())
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn verdict(page: &str) -> Option<(Verdict, Option<u64>)> {
+        Response::parse(page).map(|response| (response.verdict, response.wait_secs))
+    }
+
+    #[test]
+    fn parses_responses() {
+        assert_eq!(
+            verdict("<p>That's the right answer!  You are one gold star closer"),
+            Some((Verdict::Right, None))
+        );
+        assert_eq!(
+            verdict("That's not the right answer; your answer is too high. Please wait one minute before trying again."),
+            Some((Verdict::TooHigh, Some(60)))
+        );
+        assert_eq!(
+            verdict("That's not the right answer; your answer is too low. please wait 5 minutes"),
+            Some((Verdict::TooLow, Some(300)))
+        );
+        assert_eq!(
+            verdict("That's not the right answer. If you're stuck"),
+            Some((Verdict::Wrong, None))
+        );
+        assert_eq!(
+            verdict("You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 1m 5s left to wait."),
+            Some((Verdict::Wait, Some(65)))
+        );
+        assert_eq!(
+            verdict("You don't seem to be solving the right level.  Did you already complete it?"),
+            Some((Verdict::AlreadySolved, None))
+        );
+        assert_eq!(verdict("<html>Something else</html>"), None);
+    }
+
+    fn attempts(list: &[(&str, Verdict)]) -> Attempts {
+        Attempts {
+            path: PathBuf::new(),
+            attempts: list
+                .iter()
+                .map(|&(answer, verdict)| Attempt {
+                    part: 1,
+                    answer: answer.to_string(),
+                    verdict,
+                    at: 100,
+                    wait_secs: Some(60),
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn guards_ruled_out_answers() {
+        let log = attempts(&[
+            ("50", Verdict::TooHigh),
+            ("10", Verdict::TooLow),
+            ("30", Verdict::Wrong),
+        ]);
+        assert!(log.check(1, "30", 200).is_err());
+        assert!(log.check(1, "50", 200).is_err());
+        assert!(log.check(1, "51", 200).is_err());
+        assert!(log.check(1, "9", 200).is_err());
+        assert!(log.check(1, "20", 200).is_ok());
+        assert!(log.check(1, "20", 150).is_err());
+        assert!(log.check(2, "51", 150).is_ok());
+    }
+}
@@ -0,0 +1,59 @@
+use anyhow::bail;
+use anyhow::Result;
+use aoc_common::Day;
+use aoc_common::InputSource;
+use aoc_common::Part;
+
+/// Solves every input listed in each day's `answers.json` and compares the
+/// results, reporting each part. Fails if any answer is missing or differs.
+pub fn verify(days: &[Day]) -> Result<()> {
+    let mut checked = 0;
+    let mut failures = 0;
+    for day in days {
+        let expected = day.expected()?;
+        if expected.is_empty() {
+            println!("Day {:>2} ({}): no answers recorded", day.number, day.name);
+            continue;
+        }
+        for file in expected {
+            let source = InputSource::Path(file.input.clone());
+            let label = format!("Day {:>2} {source}", day.number);
+            let report = match source.read().and_then(|raw| {
+                (day.solve)(&raw, Part::Both).map_err(|err| err.with_file(&source).into())
+            }) {
+                Ok(report) => report,
+                Err(err) => {
+                    println!("{label}: FAILED\n{err:#}");
+                    failures += 1;
+                    continue;
+                }
+            };
+            for (part, want) in &file.parts {
+                checked += 1;
+                match report.parts.iter().find(|p| p.part == *part) {
+                    Some(got) if got.answer == *want => println!("{label} part {part}: ok"),
+                    Some(got) => {
+                        failures += 1;
+                        println!("{label} part {part}: MISMATCH");
+                        println!("  expected: {}", indent(&want.to_string()));
+                        println!("  got:      {}", indent(&got.answer.to_string()));
+                    }
+                    None => {
+                        failures += 1;
+                        println!("{label} part {part}: MISSING");
+                    }
+                }
+            }
+        }
+    }
+    if failures > 0 {
+        bail!("{failures} of {checked} answers did not verify");
+    }
+    println!("All {checked} answers verified");
+    Ok(())
+}
+
+/// Lines up multi-line answers under the first line of their label.
+fn indent(answer: &str) -> String {
+    answer.replace('\n', "\n            ")
+}
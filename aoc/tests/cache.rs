@@ -0,0 +1,78 @@
+use std::path::Path;
+use std::process::Command;
+use std::process::Output;
+
+fn aoc(cache: &Path, args: &[&str]) -> Output {
+    let root = Path::new(env!("CARGO_MANIFEST_DIR")).join("..");
+    Command::new(env!("CARGO_BIN_EXE_aoc"))
+        .args(args)
+        .current_dir(root)
+        .env("AOC_CACHE_DIR", cache)
+        .env_remove("RUST_LOG")
+        .output()
+        .expect("running aoc")
+}
+
+fn stdout(output: &Output) -> String {
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    String::from_utf8_lossy(&output.stdout).into_owned()
+}
+
+#[test]
+fn second_run_reads_the_answers_back() {
+    let cache = tempfile::tempdir().unwrap();
+    let run = ["run", "8", "eight/input2", "--time"];
+
+    let first = stdout(&aoc(cache.path(), &run));
+    assert!(first.starts_with("Part 1: 27\nPart 2: 24\n"), "{first}");
+    assert!(!first.contains("cached"), "{first}");
+
+    let second = stdout(&aoc(cache.path(), &run));
+    assert!(second.starts_with("Part 1: 27\nPart 2: 24\n"), "{second}");
+    assert_eq!(second.matches("cached").count(), 2, "{second}");
+
+    let fresh = stdout(&aoc(cache.path(), &[&run[..], &["--no-cache"]].concat()));
+    assert!(!fresh.contains("cached"), "{fresh}");
+
+    // The same input given another way still hits
+    let inline = stdout(&aoc(
+        cache.path(),
+        &[
+            "run",
+            "8",
+            "--input-str",
+            "202120211\n021121012\n022001030\n000122212\n",
+            "--time",
+        ],
+    ));
+    assert_eq!(inline.matches("cached").count(), 2, "{inline}");
+}
+
+#[test]
+fn clear_forgets_answers_but_keeps_inputs() {
+    let cache = tempfile::tempdir().unwrap();
+    let input = cache.path().join("eight/input");
+    std::fs::create_dir_all(input.parent().unwrap()).unwrap();
+    std::fs::write(&input, "30373\n25512\n65332\n33549\n35390\n").unwrap();
+
+    stdout(&aoc(cache.path(), &["run", "8"]));
+    stdout(&aoc(
+        cache.path(),
+        &["run", "6", "six/input", "--part", "1"],
+    ));
+    assert_eq!(
+        stdout(&aoc(cache.path(), &["cache", "clear", "eight"])),
+        "Removed 2 cached answers\n"
+    );
+    assert_eq!(
+        stdout(&aoc(cache.path(), &["cache", "clear"])),
+        "Removed 1 cached answer\n"
+    );
+    assert!(input.is_file());
+    let again = stdout(&aoc(cache.path(), &["run", "8", "--time"]));
+    assert!(!again.contains("cached"), "{again}");
+}
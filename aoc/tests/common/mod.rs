@@ -0,0 +1,97 @@
+// Each test binary only uses part of this
+#![allow(dead_code)]
+
+use std::collections::HashMap;
+use std::path::Path;
+use std::process::Command;
+use std::process::Output;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::thread;
+use tiny_http::Header;
+use tiny_http::Response;
+use tiny_http::Server;
+
+/// A request the stand-in site received.
+#[derive(Debug, Clone)]
+pub struct Seen {
+    pub method: String,
+    pub url: String,
+    pub cookie: Option<String>,
+    pub body: String,
+}
+
+/// A stand-in for the puzzle site on a random local port, answering each URL
+/// with a canned status and body.
+pub struct MockSite {
+    pub base_url: String,
+    pub seen: Arc<Mutex<Vec<Seen>>>,
+}
+
+impl MockSite {
+    pub fn start(routes: &[(&str, u16, &str)]) -> Self {
+        let server = Server::http("127.0.0.1:0").expect("binding the mock site");
+        let base_url = format!("http://{}", server.server_addr());
+        let routes = routes
+            .iter()
+            .map(|&(url, status, body)| (url.to_string(), (status, body.to_string())))
+            .collect::<HashMap<_, _>>();
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let log = Arc::clone(&seen);
+        thread::spawn(move || {
+            for mut request in server.incoming_requests() {
+                let mut body = String::new();
+                request.as_reader().read_to_string(&mut body).unwrap();
+                let cookie = request
+                    .headers()
+                    .iter()
+                    .find(|header| header.field.equiv("Cookie"))
+                    .map(|header| header.value.to_string());
+                log.lock().unwrap().push(Seen {
+                    method: request.method().to_string(),
+                    url: request.url().to_string(),
+                    cookie,
+                    body,
+                });
+                let (status, body) = routes
+                    .get(request.url())
+                    .cloned()
+                    .unwrap_or((404, "404 Not Found".to_string()));
+                let content_type = Header::from_bytes("Content-Type", "text/html").unwrap();
+                let response = Response::from_string(body)
+                    .with_status_code(status)
+                    .with_header(content_type);
+                let _ = request.respond(response);
+            }
+        });
+        Self { base_url, seen }
+    }
+
+    pub fn seen(&self) -> Vec<Seen> {
+        self.seen.lock().unwrap().clone()
+    }
+}
+
+/// Runs `aoc` in `dir` against `site`, logged in with `session`.
+pub fn aoc(dir: &Path, site: &MockSite, session: Option<&str>, args: &[&str]) -> Output {
+    let mut command = Command::new(env!("CARGO_BIN_EXE_aoc"));
+    command
+        .args(args)
+        .current_dir(dir)
+        .env("AOC_BASE_URL", &site.base_url)
+        .env("AOC_CACHE_DIR", dir.join("cache"))
+        .env_remove("AOC_SESSION")
+        .env("RUST_BACKTRACE", "0");
+    if let Some(session) = session {
+        command.env("AOC_SESSION", session);
+    }
+    command.output().expect("running aoc")
+}
+
+pub fn stdout(output: &Output) -> String {
+    String::from_utf8_lossy(&output.stdout).into_owned()
+}
+
+pub fn stderr(output: &Output) -> String {
+    String::from_utf8_lossy(&output.stderr).into_owned()
+}
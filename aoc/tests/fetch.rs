@@ -0,0 +1,80 @@
+mod common;
+
+use common::aoc;
+use common::stderr;
+use common::stdout;
+use common::MockSite;
+use std::fs;
+
+const EXAMPLE: &str = "1000\n2000\n3000\n\n4000\n\n5000\n6000\n\n7000\n8000\n9000\n\n10000\n";
+
+#[test]
+fn fetches_once_and_runs_cached_input() {
+    let site = MockSite::start(&[("/2022/day/1/input", 200, EXAMPLE)]);
+    let dir = tempfile::tempdir().unwrap();
+
+    let output = aoc(dir.path(), &site, Some("abc123"), &["fetch", "1"]);
+    assert!(output.status.success(), "{}", stderr(&output));
+    let cached = dir.path().join("cache/one/input");
+    assert_eq!(fs::read_to_string(&cached).unwrap(), EXAMPLE);
+    let seen = site.seen();
+    assert_eq!(seen.len(), 1);
+    assert_eq!(seen[0].method, "GET");
+    assert_eq!(seen[0].url, "/2022/day/1/input");
+    assert_eq!(seen[0].cookie.as_deref(), Some("session=abc123"));
+
+    let output = aoc(dir.path(), &site, Some("abc123"), &["fetch", "one"]);
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert!(stdout(&output).contains("already cached"));
+    assert_eq!(site.seen().len(), 1);
+
+    // There's no checked-in `one/input` here, so this must use the cache
+    let output = aoc(dir.path(), &site, None, &["run", "1"]);
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert_eq!(stdout(&output), "Part 1: 24000\nPart 2: 45000\n");
+}
+
+#[test]
+fn force_downloads_again() {
+    let site = MockSite::start(&[("/2022/day/2/input", 200, "A Y\n")]);
+    let dir = tempfile::tempdir().unwrap();
+    for _ in 0..2 {
+        let output = aoc(
+            dir.path(),
+            &site,
+            Some("abc123"),
+            &["fetch", "2", "--force"],
+        );
+        assert!(output.status.success(), "{}", stderr(&output));
+    }
+    assert_eq!(site.seen().len(), 2);
+}
+
+#[test]
+fn needs_a_session() {
+    let site = MockSite::start(&[]);
+    let dir = tempfile::tempdir().unwrap();
+    let output = aoc(dir.path(), &site, None, &["fetch", "1"]);
+    assert!(!output.status.success());
+    assert!(
+        stderr(&output).contains("AOC_SESSION"),
+        "{}",
+        stderr(&output)
+    );
+    assert!(site.seen().is_empty());
+}
+
+#[test]
+fn reports_locked_days_and_bad_sessions() {
+    let site = MockSite::start(&[("/2022/day/3/input", 400, "Please log in")]);
+    let dir = tempfile::tempdir().unwrap();
+
+    let output = aoc(dir.path(), &site, Some("expired"), &["fetch", "3"]);
+    assert!(!output.status.success());
+    assert!(stderr(&output).contains("session token was rejected"));
+
+    let output = aoc(dir.path(), &site, Some("abc123"), &["fetch", "4"]);
+    assert!(!output.status.success());
+    assert!(stderr(&output).contains("not unlocked yet"));
+    assert!(!dir.path().join("cache/four/input").exists());
+}
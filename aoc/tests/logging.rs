@@ -0,0 +1,62 @@
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+use std::process::Output;
+
+fn aoc(args: &[&str]) -> Output {
+    let root = Path::new(env!("CARGO_MANIFEST_DIR")).join("..");
+    // A fresh cache, so every run really solves and logs
+    let cache = tempfile::tempdir().unwrap();
+    Command::new(env!("CARGO_BIN_EXE_aoc"))
+        .args(args)
+        .current_dir(root)
+        .env("AOC_CACHE_DIR", cache.path())
+        .env_remove("RUST_LOG")
+        .output()
+        .expect("running aoc")
+}
+
+#[test]
+fn answers_alone_by_default() {
+    let output = aoc(&["run", "10", "ten/input2", "--part", "1"]);
+    assert!(output.status.success());
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "Part 1: 13140\n");
+    assert_eq!(String::from_utf8_lossy(&output.stderr), "");
+}
+
+#[test]
+fn verbose_logs_go_to_stderr_and_the_json_sink() {
+    let dir = tempfile::tempdir().unwrap();
+    let log = dir.path().join("log.json");
+    let output = aoc(&[
+        "run",
+        "10",
+        "ten/input2",
+        "--part",
+        "1",
+        "-vv",
+        "--log-json",
+        log.to_str().unwrap(),
+    ]);
+    assert!(output.status.success());
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "Part 1: 13140\n");
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("sampled signal strength cycle=20 x=21"),
+        "{stderr}"
+    );
+
+    let events = fs::read_to_string(log)
+        .unwrap()
+        .lines()
+        .map(|line| serde_json::from_str::<serde_json::Value>(line).unwrap())
+        .collect::<Vec<_>>();
+    let samples = events
+        .iter()
+        .filter(|event| event["fields"]["message"] == "sampled signal strength")
+        .collect::<Vec<_>>();
+    assert_eq!(samples.len(), 6);
+    assert_eq!(samples[5]["fields"]["cycle"], 220);
+    assert_eq!(samples[5]["fields"]["result"], 13140);
+    assert_eq!(samples[5]["span"]["day"], 10);
+}
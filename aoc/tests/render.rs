@@ -0,0 +1,114 @@
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+use std::process::Output;
+
+fn render(dir: &Path, args: &[&str]) -> Output {
+    let root = Path::new(env!("CARGO_MANIFEST_DIR")).join("..");
+    Command::new(env!("CARGO_BIN_EXE_aoc"))
+        .arg("render")
+        .args(args)
+        .current_dir(root)
+        .env("AOC_CACHE_DIR", dir.join("cache"))
+        .env("RUST_BACKTRACE", "0")
+        .output()
+        .expect("running aoc render")
+}
+
+#[test]
+fn single_frame_goes_to_the_given_path() {
+    let dir = tempfile::tempdir().unwrap();
+    let out = dir.path().join("trees.ppm");
+    let output = render(
+        dir.path(),
+        &["8", "--scale", "1", "-o", out.to_str().unwrap()],
+    );
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    // The checked-in grid is 99 trees square
+    assert!(fs::read(&out).unwrap().starts_with(b"P6\n99 99\n255\n"));
+}
+
+#[test]
+fn several_stills_are_numbered_and_keep_the_last() {
+    let dir = tempfile::tempdir().unwrap();
+    let out = dir.path().join("crt.png");
+    let output = render(
+        dir.path(),
+        &[
+            "ten",
+            "ten/input2",
+            "--every",
+            "100",
+            "-o",
+            out.to_str().unwrap(),
+        ],
+    );
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    // Frames 1, 101 and 201 of 240, plus the last
+    let mut names = fs::read_dir(dir.path())
+        .unwrap()
+        .map(|entry| entry.unwrap().file_name().into_string().unwrap())
+        .filter(|name| name.starts_with("crt"))
+        .collect::<Vec<_>>();
+    names.sort();
+    assert_eq!(
+        names,
+        [
+            "crt-00001.png",
+            "crt-00002.png",
+            "crt-00003.png",
+            "crt-00004.png"
+        ]
+    );
+}
+
+#[test]
+fn gif_of_the_rope() {
+    let dir = tempfile::tempdir().unwrap();
+    let out = dir.path().join("rope.gif");
+    let output = render(
+        dir.path(),
+        &[
+            "9",
+            "--input-str",
+            "R 4\nU 4\nL 3\nD 1\nR 4\nD 1\nL 5\nR 2\n",
+            "--view",
+            "short-rope",
+            "-o",
+            out.to_str().unwrap(),
+        ],
+    );
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert!(String::from_utf8_lossy(&output.stdout).contains("Wrote 9 frames"));
+    assert!(fs::read(&out).unwrap().starts_with(b"GIF89a"));
+}
+
+#[test]
+fn rejects_unknown_views_and_formats() {
+    let dir = tempfile::tempdir().unwrap();
+    let out = dir.path().join("x.gif");
+    let output = render(
+        dir.path(),
+        &["10", "--view", "beam", "-o", out.to_str().unwrap()],
+    );
+    assert!(String::from_utf8_lossy(&output.stderr).contains("pick one of: crt"));
+    let output = render(dir.path(), &["3", "-o", out.to_str().unwrap()]);
+    assert!(String::from_utf8_lossy(&output.stderr).contains("nothing to render"));
+    let jpg = dir.path().join("x.jpg");
+    assert!(!render(dir.path(), &["8", "-o", jpg.to_str().unwrap()])
+        .status
+        .success());
+    assert!(!out.exists());
+}
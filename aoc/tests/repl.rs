@@ -0,0 +1,72 @@
+use std::io::Write;
+use std::path::Path;
+use std::process::Command;
+use std::process::Output;
+use std::process::Stdio;
+
+fn repl(args: &[&str], commands: &str) -> Output {
+    let root = Path::new(env!("CARGO_MANIFEST_DIR")).join("..");
+    let cache = tempfile::tempdir().unwrap();
+    let mut child = Command::new(env!("CARGO_BIN_EXE_aoc"))
+        .arg("repl")
+        .args(args)
+        .current_dir(root)
+        .env("AOC_CACHE_DIR", cache.path())
+        .env_remove("RUST_LOG")
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .expect("running aoc");
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(commands.as_bytes())
+        .unwrap();
+    child.wait_with_output().expect("running aoc")
+}
+
+#[test]
+fn answers_piped_commands() {
+    let output = repl(
+        &["8", "eight/input2"],
+        "score 1 2\n\nbest\nnonsense\nvisible 9 9\nquit\nbest\n",
+    );
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert_eq!(stdout.lines().count(), 2, "{stdout}");
+    assert!(stdout.starts_with("1 ("), "{stdout}");
+    assert!(stdout.lines().nth(1).unwrap().ends_with(": 24"), "{stdout}");
+    assert!(stderr.contains("unknown command \"nonsense\""), "{stderr}");
+    assert_eq!(stderr.matches("error:").count(), 2, "{stderr}");
+}
+
+#[test]
+fn help_lists_the_days_commands() {
+    let output = repl(
+        &[
+            "5",
+            "--input-str",
+            "    [D]\n[N] [C]\n[Z] [M] [P]\n 1   2   3\n\nmove 1 from 2 to 1\n",
+        ],
+        "help\n",
+    );
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    for command in ["stack N", "step [N]", "help", "quit"] {
+        assert!(stdout.contains(command), "{stdout}");
+    }
+}
+
+#[test]
+fn days_without_a_repl_say_so() {
+    let output = repl(&["6", "--input-str", "abcd"], "");
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("day 6 has no repl"));
+}
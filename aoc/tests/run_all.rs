@@ -0,0 +1,25 @@
+use std::path::Path;
+use std::process::Command;
+
+#[test]
+fn every_day_passes_in_the_summary() {
+    let root = Path::new(env!("CARGO_MANIFEST_DIR")).join("..");
+    let cache = tempfile::tempdir().unwrap();
+    let output = Command::new(env!("CARGO_BIN_EXE_aoc"))
+        .args(["run", "all"])
+        .current_dir(root)
+        .env("AOC_CACHE_DIR", cache.path())
+        .env_remove("RUST_LOG")
+        .output()
+        .expect("running aoc run all");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        output.status.success(),
+        "aoc run all failed:\n{stdout}{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let rows = stdout.lines().filter(|line| line.ends_with("pass")).count();
+    assert_eq!(rows, 10, "{stdout}");
+    assert!(stdout.contains("10 days in"), "{stdout}");
+    assert!(stdout.contains("none failed"), "{stdout}");
+}
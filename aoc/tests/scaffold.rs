@@ -0,0 +1,62 @@
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+
+/// Copies the files `aoc new` edits into a scratch workspace.
+fn scratch_workspace() -> tempfile::TempDir {
+    let root = Path::new(env!("CARGO_MANIFEST_DIR")).join("..");
+    let dir = tempfile::tempdir().unwrap();
+    for file in ["Cargo.toml", "aoc/Cargo.toml", "aoc/src/registry.rs"] {
+        let to = dir.path().join(file);
+        fs::create_dir_all(to.parent().unwrap()).unwrap();
+        fs::copy(root.join(file), to).unwrap();
+    }
+    dir
+}
+
+fn aoc_new(dir: &Path, day: &str) -> std::process::Output {
+    Command::new(env!("CARGO_BIN_EXE_aoc"))
+        .args(["new", day])
+        .current_dir(dir)
+        .env("RUST_BACKTRACE", "0")
+        .output()
+        .expect("running aoc new")
+}
+
+#[test]
+fn creates_and_registers_a_day() {
+    let dir = scratch_workspace();
+    let output = aoc_new(dir.path(), "11");
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let read = |file: &str| fs::read_to_string(dir.path().join(file)).unwrap();
+    let lib = read("eleven/src/lib.rs");
+    assert!(lib.contains("pub const DAY: Day = Day::new::<Eleven>(11, \"eleven\");"));
+    assert!(lib.contains("impl Solution for Eleven"));
+    assert!(lib.contains("mod tests"));
+    assert!(read("eleven/src/main.rs").contains("aoc_common::main(&eleven::DAY)"));
+    assert!(read("eleven/Cargo.toml").contains("name = \"eleven\""));
+    assert!(read("Cargo.toml").contains("    \"ten\",\n    \"eleven\",\n    \"aoc\",\n"));
+    assert!(read("aoc/Cargo.toml")
+        .contains("ten = { path = \"../ten\" }\neleven = { path = \"../eleven\" }\n"));
+    assert!(read("aoc/src/registry.rs").contains("    ten::DAY,\n    eleven::DAY,\n];"));
+
+    // A second run must not clobber the new crate
+    assert!(!aoc_new(dir.path(), "11").status.success());
+}
+
+#[test]
+fn rejects_registered_and_impossible_days() {
+    let dir = scratch_workspace();
+    assert!(!aoc_new(dir.path(), "7").status.success());
+    assert!(!aoc_new(dir.path(), "26").status.success());
+    assert!(!aoc_new(dir.path(), "0").status.success());
+    assert_eq!(
+        fs::read_to_string(dir.path().join("Cargo.toml")).unwrap(),
+        fs::read_to_string(Path::new(env!("CARGO_MANIFEST_DIR")).join("../Cargo.toml")).unwrap()
+    );
+}
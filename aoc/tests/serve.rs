@@ -0,0 +1,117 @@
+use std::io::BufRead;
+use std::io::BufReader;
+use std::io::Read;
+use std::path::Path;
+use std::process::Child;
+use std::process::Command;
+use std::process::Stdio;
+use tempfile::TempDir;
+
+/// `aoc serve` on a free port, killed when dropped.
+struct Served {
+    child: Child,
+    base_url: String,
+    _cache: TempDir,
+}
+
+impl Served {
+    fn start() -> Self {
+        let root = Path::new(env!("CARGO_MANIFEST_DIR")).join("..");
+        let cache = tempfile::tempdir().unwrap();
+        let mut child = Command::new(env!("CARGO_BIN_EXE_aoc"))
+            .args(["serve", "--port", "0"])
+            .current_dir(root)
+            .env("AOC_CACHE_DIR", cache.path())
+            .env_remove("RUST_LOG")
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .spawn()
+            .expect("running aoc");
+        let mut line = String::new();
+        BufReader::new(child.stdout.take().unwrap())
+            .read_line(&mut line)
+            .unwrap();
+        let base_url = line
+            .trim()
+            .strip_prefix("Serving on ")
+            .unwrap_or_else(|| panic!("unexpected first line {line:?}"))
+            .trim_end_matches('/')
+            .to_string();
+        Self {
+            child,
+            base_url,
+            _cache: cache,
+        }
+    }
+
+    fn get(&self, path: &str) -> (u16, String, Vec<u8>) {
+        let response = match ureq::get(&format!("{}{path}", self.base_url)).call() {
+            Ok(response) | Err(ureq::Error::Status(_, response)) => response,
+            Err(err) => panic!("GET {path}: {err}"),
+        };
+        let status = response.status();
+        let content_type = response.content_type().to_string();
+        let mut body = Vec::new();
+        response.into_reader().read_to_end(&mut body).unwrap();
+        (status, content_type, body)
+    }
+
+    fn page(&self, path: &str) -> String {
+        let (status, content_type, body) = self.get(path);
+        assert_eq!(status, 200, "{path}");
+        assert_eq!(content_type, "text/html", "{path}");
+        String::from_utf8(body).unwrap()
+    }
+}
+
+impl Drop for Served {
+    fn drop(&mut self) {
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
+
+#[test]
+fn index_lists_every_day() {
+    let served = Served::start();
+    assert!(
+        served.base_url.starts_with("http://127.0.0.1:"),
+        "{}",
+        served.base_url
+    );
+    let index = served.page("/");
+    assert_eq!(index.matches("<a href=\"/day/").count(), 10, "{index}");
+    assert!(index.contains("<code>1334506</code>"), "{index}");
+    assert!(index.contains("10 days, none failed"), "{index}");
+    assert!(
+        !index.contains("http"),
+        "pages pull nothing in from elsewhere"
+    );
+}
+
+#[test]
+fn day_pages_show_pictures_and_trees() {
+    let served = Served::start();
+
+    let ten = served.page("/day/10");
+    assert!(ten.contains("<pre>###..#..#.###"), "{ten}");
+    assert!(ten.contains("src=\"/day/10/crt/239.png\""), "{ten}");
+
+    let eight = served.page("/day/eight");
+    assert!(eight.contains("src=\"/day/8/scenic/0.png\""), "{eight}");
+    assert!(
+        !eight.contains("<input type=range"),
+        "stills have nothing to scrub"
+    );
+
+    let seven = served.page("/day/7");
+    assert!(seven.contains("<summary>/ (46975962)</summary>"), "{seven}");
+
+    let (status, content_type, png) = served.get("/day/10/crt/0.png");
+    assert_eq!((status, content_type.as_str()), (200, "image/png"));
+    assert!(png.starts_with(b"\x89PNG"));
+
+    assert_eq!(served.get("/day/10/crt/240.png").0, 404);
+    assert_eq!(served.get("/day/10/rope/0.png").0, 404);
+    assert_eq!(served.get("/day/11").0, 404);
+}
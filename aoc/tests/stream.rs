@@ -0,0 +1,54 @@
+use std::path::Path;
+use std::process::Command;
+use std::process::Output;
+
+fn aoc(args: &[&str]) -> Output {
+    let root = Path::new(env!("CARGO_MANIFEST_DIR")).join("..");
+    let cache = tempfile::tempdir().unwrap();
+    Command::new(env!("CARGO_BIN_EXE_aoc"))
+        .args(args)
+        .current_dir(root)
+        .env("AOC_CACHE_DIR", cache.path())
+        .env_remove("RUST_LOG")
+        .output()
+        .expect("running aoc")
+}
+
+fn stdout(output: &Output) -> String {
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    String::from_utf8_lossy(&output.stdout).into_owned()
+}
+
+#[test]
+fn parts_are_solved_and_timed_separately_by_default() {
+    for day in ["1", "9", "10"] {
+        let out = stdout(&aoc(&["run", day, "--part", "1", "--time"]));
+        assert!(out.starts_with("Part 1: "), "{out}");
+        assert!(!out.contains("Part 2"), "{out}");
+        let timing = |stage: &str| {
+            out.lines()
+                .find(|line| line.trim_start().starts_with(stage))
+                .unwrap_or_else(|| panic!("no {stage} timing in {out}"))
+                .to_string()
+        };
+        assert!(!timing("parse").ends_with(" 0.000ns"), "{out}");
+        assert!(!timing("part 1").ends_with(" 0.000ns"), "{out}");
+        assert!(!out.contains("part 2"), "{out}");
+    }
+}
+
+#[test]
+fn streaming_is_asked_for() {
+    let read = stdout(&aoc(&["run", "9", "nine/input"]));
+    let streamed = stdout(&aoc(&["run", "9", "nine/input", "--stream"]));
+    assert_eq!(read, streamed);
+    assert_eq!(streamed, "Part 1: 6284\nPart 2: 2661\n");
+
+    let output = aoc(&["run", "9", "nine/input", "--stream", "--part", "1"]);
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("cannot be used with"));
+}
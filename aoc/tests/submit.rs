@@ -0,0 +1,107 @@
+mod common;
+
+use common::aoc;
+use common::stderr;
+use common::stdout;
+use common::MockSite;
+use std::fs;
+use std::path::Path;
+
+const EXAMPLE: &str = "1000\n2000\n3000\n\n4000\n\n5000\n6000\n\n7000\n8000\n9000\n\n10000\n";
+const TOO_HIGH: &str = "<main><article><p>That's not the right answer; your answer is too \
+    high.  If you're stuck, make sure you're using the full input data.  Please wait one \
+    minute before trying again.</p></article></main>";
+const TOO_RECENT: &str = "<main><article><p>You gave an answer too recently; you have to wait \
+    after submitting an answer before trying again.  You have 45s left to wait.</p></article></main>";
+const RIGHT: &str = "<main><article><p>That's the right answer!  You are one gold star closer \
+    to collecting enough star fruit.</p></article></main>";
+
+fn cache_input(dir: &Path, day: &str, input: &str) {
+    let path = dir.join("cache").join(day);
+    fs::create_dir_all(&path).unwrap();
+    fs::write(path.join("input"), input).unwrap();
+}
+
+#[test]
+fn submits_the_computed_answer() {
+    let site = MockSite::start(&[("/2022/day/1/answer", 200, RIGHT)]);
+    let dir = tempfile::tempdir().unwrap();
+    cache_input(dir.path(), "one", EXAMPLE);
+
+    let output = aoc(dir.path(), &site, Some("abc123"), &["submit", "1", "2"]);
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert_eq!(stdout(&output), "Day 1 part 2: 45000 is right\n");
+    let seen = site.seen();
+    assert_eq!(seen.len(), 1);
+    assert_eq!(seen[0].method, "POST");
+    assert_eq!(seen[0].body, "level=2&answer=45000");
+    assert_eq!(seen[0].cookie.as_deref(), Some("session=abc123"));
+
+    // Solved parts are never submitted again
+    let output = aoc(dir.path(), &site, Some("abc123"), &["submit", "1", "2"]);
+    assert!(!output.status.success());
+    assert!(stderr(&output).contains("already solved"));
+    assert_eq!(site.seen().len(), 1);
+}
+
+#[test]
+fn never_resubmits_ruled_out_answers() {
+    let site = MockSite::start(&[("/2022/day/1/answer", 200, TOO_HIGH)]);
+    let dir = tempfile::tempdir().unwrap();
+    cache_input(dir.path(), "one", EXAMPLE);
+
+    let output = aoc(dir.path(), &site, Some("abc123"), &["submit", "1", "1"]);
+    assert!(!output.status.success());
+    assert_eq!(stdout(&output), "Day 1 part 1: 24000 is wrong, too high\n");
+    assert!(stderr(&output).contains("wait 60s"), "{}", stderr(&output));
+    assert_eq!(site.seen().len(), 1);
+
+    for answer in ["24000", "25000"] {
+        let output = aoc(
+            dir.path(),
+            &site,
+            Some("abc123"),
+            &["submit", "1", "1", "--answer", answer],
+        );
+        assert!(!output.status.success());
+        assert_eq!(site.seen().len(), 1, "{answer} was resubmitted");
+    }
+    let attempts = fs::read_to_string(dir.path().join("cache/one/attempts.json")).unwrap();
+    assert!(attempts.contains("\"too_high\""), "{attempts}");
+}
+
+#[test]
+fn honours_waits() {
+    let site = MockSite::start(&[("/2022/day/4/answer", 200, TOO_RECENT)]);
+    let dir = tempfile::tempdir().unwrap();
+
+    let args = ["submit", "4", "1", "--answer", "3"];
+    let output = aoc(dir.path(), &site, Some("abc123"), &args);
+    assert!(!output.status.success());
+    assert!(stderr(&output).contains("wait 45s"), "{}", stderr(&output));
+
+    let output = aoc(
+        dir.path(),
+        &site,
+        Some("abc123"),
+        &["submit", "4", "1", "--answer", "4"],
+    );
+    assert!(!output.status.success());
+    assert!(
+        stderr(&output).contains("asked to wait"),
+        "{}",
+        stderr(&output)
+    );
+    assert_eq!(site.seen().len(), 1);
+}
+
+#[test]
+fn pictures_need_an_answer() {
+    let site = MockSite::start(&[]);
+    let dir = tempfile::tempdir().unwrap();
+    cache_input(dir.path(), "ten", "noop\n");
+    let output = aoc(dir.path(), &site, Some("abc123"), &["submit", "10", "2"]);
+    assert!(!output.status.success());
+    assert!(stderr(&output).contains("--answer"));
+    assert!(site.seen().is_empty());
+}
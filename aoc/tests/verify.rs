@@ -0,0 +1,19 @@
+use std::path::Path;
+use std::process::Command;
+
+#[test]
+fn recorded_answers_match() {
+    let root = Path::new(env!("CARGO_MANIFEST_DIR")).join("..");
+    let output = Command::new(env!("CARGO_BIN_EXE_aoc"))
+        .arg("verify")
+        .current_dir(root)
+        .output()
+        .expect("running aoc verify");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        output.status.success(),
+        "aoc verify failed:\n{stdout}{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert!(!stdout.contains("MISMATCH"), "{stdout}");
+}
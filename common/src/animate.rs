@@ -0,0 +1,314 @@
+use crate::Part;
+use crate::Solution;
+use anyhow::Result;
+use crossterm::cursor;
+use crossterm::event;
+use crossterm::event::Event;
+use crossterm::event::KeyCode;
+use crossterm::event::KeyEvent;
+use crossterm::event::KeyEventKind;
+use crossterm::event::KeyModifiers;
+use crossterm::queue;
+use crossterm::terminal;
+use crossterm::terminal::ClearType;
+use std::io;
+use std::io::IsTerminal;
+use std::io::Write;
+use std::thread;
+use std::time::Duration;
+use std::time::Instant;
+
+/// A puzzle's state played back in the terminal, one frame at a time.
+///
+/// Frame 0 is the starting state and every `step` moves one frame on.
+/// Seeking backwards resets and steps forward again, so stepping has to
+/// be cheap.
+pub trait Animation {
+    /// How many frames there are, counting the starting state.
+    fn frames(&self) -> usize;
+    fn reset(&mut self);
+    fn step(&mut self);
+    /// Draws the current frame in at most `width` by `height` characters.
+    fn draw(&self, width: usize, height: usize) -> String;
+}
+
+/// A day that can be watched with `--animate`.
+pub trait Animate: Solution {
+    /// The animation for `part`, each day picking what `Part::Both` shows.
+    fn animation(input: &Self::Input, part: Part) -> Box<dyn Animation + '_>;
+}
+
+/// How a [`crate::Day`] plays its animation.
+pub type AnimateFn = fn(&str, Part, u32) -> Result<()>;
+
+pub(crate) fn animate<S: Animate>(input: &str, part: Part, fps: u32) -> Result<()> {
+    let input = S::parse(input)?;
+    play(S::animation(&input, part).as_mut(), fps)?;
+    Ok(())
+}
+
+/// What a key press asks the player to do.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Control {
+    Continue,
+    Quit,
+}
+
+/// Playback state: which frame is showing, whether it's running and how fast.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Player {
+    frame: usize,
+    len: usize,
+    playing: bool,
+    fps: u32,
+}
+
+impl Player {
+    fn new(len: usize, fps: u32) -> Self {
+        Self {
+            frame: 0,
+            len: len.max(1),
+            playing: true,
+            fps: fps.clamp(1, 1000),
+        }
+    }
+
+    fn last(&self) -> usize {
+        self.len - 1
+    }
+
+    fn seek(&mut self, frame: usize) {
+        self.frame = frame.min(self.last());
+    }
+
+    /// Moves on a frame while playing, stopping at the end.
+    fn tick(&mut self) {
+        if self.playing {
+            self.seek(self.frame + 1);
+            self.playing = self.frame < self.last();
+        }
+    }
+
+    fn key(&mut self, key: KeyCode) -> Control {
+        let jump = (self.len / 10).max(1);
+        match key {
+            KeyCode::Char('q') | KeyCode::Esc => return Control::Quit,
+            KeyCode::Char(' ') => {
+                if self.frame == self.last() {
+                    self.frame = 0;
+                }
+                self.playing = !self.playing;
+            }
+            KeyCode::Right | KeyCode::Char('l') => {
+                self.playing = false;
+                self.seek(self.frame + 1);
+            }
+            KeyCode::Left | KeyCode::Char('h') => {
+                self.playing = false;
+                self.seek(self.frame.saturating_sub(1));
+            }
+            KeyCode::Char(']') | KeyCode::PageDown => self.seek(self.frame + jump),
+            KeyCode::Char('[') | KeyCode::PageUp => self.seek(self.frame.saturating_sub(jump)),
+            KeyCode::Home | KeyCode::Char('g') => self.seek(0),
+            KeyCode::End | KeyCode::Char('G') => self.seek(self.last()),
+            KeyCode::Char(digit @ '0'..='9') => {
+                let tenths = digit.to_digit(10).expect("matched a digit") as usize;
+                self.seek(self.last() * tenths / 10);
+            }
+            KeyCode::Char('+') | KeyCode::Char('=') => self.fps = (self.fps * 2).min(1000),
+            KeyCode::Char('-') => self.fps = (self.fps / 2).max(1),
+            _ => {}
+        }
+        Control::Continue
+    }
+
+    fn status(&self) -> String {
+        format!(
+            "frame {}/{}  {} fps{}  space pause  \u{2190}/\u{2192} step  [/] or 0-9 seek  +/- speed  q quit",
+            self.frame,
+            self.last(),
+            self.fps,
+            if self.playing { "" } else { "  (paused)" },
+        )
+    }
+}
+
+/// Restores the terminal however playback ends.
+struct Screen;
+
+impl Screen {
+    fn enter(out: &mut impl Write) -> io::Result<Self> {
+        terminal::enable_raw_mode()?;
+        queue!(out, terminal::EnterAlternateScreen, cursor::Hide)?;
+        Ok(Screen)
+    }
+}
+
+impl Drop for Screen {
+    fn drop(&mut self) {
+        let mut out = io::stdout();
+        let _ = queue!(out, cursor::Show, terminal::LeaveAlternateScreen);
+        let _ = out.flush();
+        let _ = terminal::disable_raw_mode();
+    }
+}
+
+/// Plays the animation in place at `fps` frames a second. On a terminal the
+/// keys listed in the status line control it; otherwise it just plays
+/// through once.
+pub fn play(animation: &mut dyn Animation, fps: u32) -> io::Result<()> {
+    let mut out = io::stdout().lock();
+    let interactive = io::stdin().is_terminal() && out.is_terminal();
+    let _screen = if interactive {
+        Some(Screen::enter(&mut out)?)
+    } else {
+        None
+    };
+    let mut player = Player::new(animation.frames(), fps);
+    animation.reset();
+    let mut shown = 0;
+    loop {
+        if player.frame < shown {
+            animation.reset();
+            shown = 0;
+        }
+        while shown < player.frame {
+            animation.step();
+            shown += 1;
+        }
+        let (width, height) = terminal::size().map_or((80, 24), |(w, h)| (w as usize, h as usize));
+        draw(&mut out, animation, &player, width, height)?;
+
+        let next = Instant::now() + Duration::from_secs(1) / player.fps;
+        if !interactive {
+            if !player.playing {
+                break;
+            }
+            thread::sleep(next.saturating_duration_since(Instant::now()));
+            player.tick();
+            continue;
+        }
+        let mut redraw = false;
+        while !redraw {
+            let wait = if player.playing {
+                next.saturating_duration_since(Instant::now())
+            } else {
+                Duration::from_secs(60)
+            };
+            if !event::poll(wait)? {
+                player.tick();
+                break;
+            }
+            match event::read()? {
+                Event::Key(key) if key.kind != KeyEventKind::Release => {
+                    if interrupts(&key) || player.key(key.code) == Control::Quit {
+                        return Ok(());
+                    }
+                    redraw = true;
+                }
+                Event::Resize(..) => redraw = true,
+                _ => {}
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Whether `key` is Ctrl-C, which raw mode delivers as a key press
+/// instead of a SIGINT.
+fn interrupts(key: &KeyEvent) -> bool {
+    key.modifiers.contains(KeyModifiers::CONTROL) && key.code == KeyCode::Char('c')
+}
+
+fn draw(
+    out: &mut impl Write,
+    animation: &dyn Animation,
+    player: &Player,
+    width: usize,
+    height: usize,
+) -> io::Result<()> {
+    queue!(out, cursor::MoveTo(0, 0))?;
+    let status = player.status();
+    let frame = animation.draw(width, height.saturating_sub(1));
+    for line in std::iter::once(status.as_str()).chain(frame.lines()) {
+        let line = line.chars().take(width).collect::<String>();
+        queue!(out, terminal::Clear(ClearType::UntilNewLine))?;
+        // Raw mode doesn't turn `\n` into a carriage return
+        write!(out, "{line}\r\n")?;
+    }
+    queue!(out, terminal::Clear(ClearType::FromCursorDown))?;
+    out.flush()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn plays_to_the_end_and_stops() {
+        let mut player = Player::new(3, 10);
+        player.tick();
+        player.tick();
+        assert_eq!((player.frame, player.playing), (2, false));
+        player.tick();
+        assert_eq!(player.frame, 2);
+        // Playing again from the end starts over
+        player.key(KeyCode::Char(' '));
+        assert_eq!((player.frame, player.playing), (0, true));
+    }
+
+    #[test]
+    fn stepping_pauses() {
+        let mut player = Player::new(100, 10);
+        player.key(KeyCode::Right);
+        assert_eq!((player.frame, player.playing), (1, false));
+        player.key(KeyCode::Left);
+        player.key(KeyCode::Left);
+        assert_eq!(player.frame, 0);
+        player.tick();
+        assert_eq!(player.frame, 0);
+    }
+
+    #[test]
+    fn seeking_stays_in_range() {
+        let mut player = Player::new(101, 10);
+        player.key(KeyCode::Char('5'));
+        assert_eq!(player.frame, 50);
+        player.key(KeyCode::Char(']'));
+        assert_eq!(player.frame, 60);
+        player.key(KeyCode::End);
+        player.key(KeyCode::Char(']'));
+        assert_eq!(player.frame, 100);
+        player.key(KeyCode::Home);
+        player.key(KeyCode::Char('['));
+        assert_eq!(player.frame, 0);
+        assert_eq!(player.key(KeyCode::Char('q')), Control::Quit);
+    }
+
+    #[test]
+    fn ctrl_c_interrupts() {
+        assert!(interrupts(&KeyEvent::new(
+            KeyCode::Char('c'),
+            KeyModifiers::CONTROL
+        )));
+        assert!(!interrupts(&KeyEvent::new(
+            KeyCode::Char('c'),
+            KeyModifiers::NONE
+        )));
+        assert!(!interrupts(&KeyEvent::new(
+            KeyCode::Char('x'),
+            KeyModifiers::CONTROL
+        )));
+    }
+
+    #[test]
+    fn speed_doubles_and_halves() {
+        let mut player = Player::new(10, 10);
+        player.key(KeyCode::Char('+'));
+        assert_eq!(player.fps, 20);
+        for _ in 0..10 {
+            player.key(KeyCode::Char('-'));
+        }
+        assert_eq!(player.fps, 1);
+    }
+}
@@ -0,0 +1,126 @@
+use anyhow::anyhow;
+use anyhow::bail;
+use anyhow::Context;
+use anyhow::Result;
+use serde::Deserialize;
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs;
+use std::path::Path;
+use std::path::PathBuf;
+
+/// A puzzle answer, in a form both the text and JSON output understand.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", content = "answer", rename_all = "lowercase")]
+pub enum Answer {
+    Integer(i128),
+    Text(String),
+    /// A multi-line answer, such as a rendered screen, one entry per row.
+    Rows(Vec<String>),
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Answer::Integer(val) => write!(f, "{val}"),
+            Answer::Text(val) => write!(f, "{val}"),
+            Answer::Rows(rows) => write!(f, "{}", rows.join("\n")),
+        }
+    }
+}
+
+macro_rules! integer_answer {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for Answer {
+                fn from(val: $t) -> Self {
+                    Answer::Integer(val as i128)
+                }
+            }
+        )*
+    };
+}
+
+integer_answer!(u8, u16, u32, u64, usize, i8, i16, i32, i64, isize);
+
+impl From<String> for Answer {
+    fn from(val: String) -> Self {
+        Answer::Text(val)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(val: &str) -> Self {
+        Answer::Text(val.to_string())
+    }
+}
+
+impl Answer {
+    /// Reads a plain JSON value: numbers are integers, strings are text and
+    /// arrays of strings are rows.
+    pub fn from_json(value: &Value) -> Option<Self> {
+        match value {
+            Value::Number(val) => val
+                .as_i64()
+                .map(i128::from)
+                .or_else(|| val.as_u64().map(i128::from))
+                .map(Answer::Integer),
+            Value::String(val) => Some(Answer::Text(val.clone())),
+            Value::Array(rows) => rows
+                .iter()
+                .map(|row| row.as_str().map(str::to_string))
+                .collect::<Option<Vec<_>>>()
+                .map(Answer::Rows),
+            _ => None,
+        }
+    }
+}
+
+/// The known answers for one input file, as recorded in `<day>/answers.json`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Expected {
+    pub input: PathBuf,
+    pub parts: Vec<(u8, Answer)>,
+}
+
+impl Expected {
+    pub fn part(&self, part: u8) -> Option<&Answer> {
+        self.parts
+            .iter()
+            .find(|(p, _)| *p == part)
+            .map(|(_, answer)| answer)
+    }
+}
+
+/// Loads `answers.json` from a day's directory. The file maps each input file
+/// name to its `part1` and `part2` answers; a missing file means no answers.
+pub fn load_expected(dir: &Path) -> Result<Vec<Expected>> {
+    let path = dir.join("answers.json");
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    let raw = fs::read_to_string(&path).with_context(|| format!("reading {}", path.display()))?;
+    let files: BTreeMap<String, BTreeMap<String, Value>> =
+        serde_json::from_str(&raw).with_context(|| format!("parsing {}", path.display()))?;
+    let mut res = Vec::new();
+    for (input, parts) in files {
+        let mut expected = Expected {
+            input: dir.join(&input),
+            parts: Vec::new(),
+        };
+        for (key, value) in parts {
+            let part = match key.as_str() {
+                "part1" => 1,
+                "part2" => 2,
+                other => bail!("{}: unknown key {other:?} for {input}", path.display()),
+            };
+            let answer = Answer::from_json(&value)
+                .ok_or_else(|| anyhow!("{}: unsupported answer for {input}", path.display()))?;
+            expected.parts.push((part, answer));
+        }
+        res.push(expected);
+    }
+    Ok(res)
+}
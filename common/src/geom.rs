@@ -0,0 +1,248 @@
+use anyhow::anyhow;
+use anyhow::Error;
+use std::fmt;
+use std::iter::Sum;
+use std::ops::Add;
+use std::ops::AddAssign;
+use std::ops::Mul;
+use std::ops::Neg;
+use std::ops::Sub;
+use std::ops::SubAssign;
+use std::str::FromStr;
+
+/// A signed 2D position or vector, with `y` growing downwards like a grid's
+/// rows.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Point {
+    pub x: i64,
+    pub y: i64,
+}
+
+impl Point {
+    pub const ORIGIN: Point = Point::new(0, 0);
+
+    pub const fn new(x: i64, y: i64) -> Self {
+        Self { x, y }
+    }
+
+    pub fn manhattan(self, other: Point) -> u64 {
+        self.x.abs_diff(other.x) + self.y.abs_diff(other.y)
+    }
+
+    /// The distance counting diagonal steps as one, so touching points are 1
+    /// apart.
+    pub fn chebyshev(self, other: Point) -> u64 {
+        self.x.abs_diff(other.x).max(self.y.abs_diff(other.y))
+    }
+
+    /// The vector with each coordinate clamped to -1, 0 or 1: one king's move
+    /// in the same general direction.
+    pub fn signum(self) -> Point {
+        Point::new(self.x.signum(), self.y.signum())
+    }
+}
+
+impl Add for Point {
+    type Output = Point;
+
+    fn add(self, other: Point) -> Point {
+        Point::new(self.x + other.x, self.y + other.y)
+    }
+}
+
+impl AddAssign for Point {
+    fn add_assign(&mut self, other: Point) {
+        *self = *self + other;
+    }
+}
+
+impl Sum for Point {
+    fn sum<I: Iterator<Item = Point>>(iter: I) -> Point {
+        iter.fold(Point::ORIGIN, Add::add)
+    }
+}
+
+impl Sub for Point {
+    type Output = Point;
+
+    fn sub(self, other: Point) -> Point {
+        Point::new(self.x - other.x, self.y - other.y)
+    }
+}
+
+impl SubAssign for Point {
+    fn sub_assign(&mut self, other: Point) {
+        *self = *self - other;
+    }
+}
+
+impl Mul<i64> for Point {
+    type Output = Point;
+
+    fn mul(self, scale: i64) -> Point {
+        Point::new(self.x * scale, self.y * scale)
+    }
+}
+
+impl Neg for Point {
+    type Output = Point;
+
+    fn neg(self) -> Point {
+        Point::new(-self.x, -self.y)
+    }
+}
+
+impl fmt::Display for Point {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "({}, {})", self.x, self.y)
+    }
+}
+
+/// One of the four orthogonal directions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Dir4 {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+impl Dir4 {
+    /// Clockwise from `Up`.
+    pub const ALL: [Dir4; 4] = [Dir4::Up, Dir4::Right, Dir4::Down, Dir4::Left];
+
+    pub fn step(self) -> Point {
+        match self {
+            Dir4::Up => Point::new(0, -1),
+            Dir4::Right => Point::new(1, 0),
+            Dir4::Down => Point::new(0, 1),
+            Dir4::Left => Point::new(-1, 0),
+        }
+    }
+}
+
+impl From<Dir4> for Point {
+    fn from(dir: Dir4) -> Point {
+        dir.step()
+    }
+}
+
+/// Parses the `U`, `D`, `L` and `R` used in puzzle inputs.
+impl FromStr for Dir4 {
+    type Err = Error;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let dir = match s {
+            "U" => Dir4::Up,
+            "R" => Dir4::Right,
+            "D" => Dir4::Down,
+            "L" => Dir4::Left,
+            _ => return Err(anyhow!("invalid direction {s:?}, expected U, D, L or R")),
+        };
+        Ok(dir)
+    }
+}
+
+impl fmt::Display for Dir4 {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let val = match self {
+            Dir4::Up => "U",
+            Dir4::Right => "R",
+            Dir4::Down => "D",
+            Dir4::Left => "L",
+        };
+        write!(f, "{val}")
+    }
+}
+
+/// One of the eight compass directions, orthogonal or diagonal.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Dir8 {
+    N,
+    NE,
+    E,
+    SE,
+    S,
+    SW,
+    W,
+    NW,
+}
+
+impl Dir8 {
+    /// Clockwise from `N`.
+    pub const ALL: [Dir8; 8] = [
+        Dir8::N,
+        Dir8::NE,
+        Dir8::E,
+        Dir8::SE,
+        Dir8::S,
+        Dir8::SW,
+        Dir8::W,
+        Dir8::NW,
+    ];
+
+    pub fn step(self) -> Point {
+        match self {
+            Dir8::N => Point::new(0, -1),
+            Dir8::NE => Point::new(1, -1),
+            Dir8::E => Point::new(1, 0),
+            Dir8::SE => Point::new(1, 1),
+            Dir8::S => Point::new(0, 1),
+            Dir8::SW => Point::new(-1, 1),
+            Dir8::W => Point::new(-1, 0),
+            Dir8::NW => Point::new(-1, -1),
+        }
+    }
+}
+
+impl From<Dir8> for Point {
+    fn from(dir: Dir8) -> Point {
+        dir.step()
+    }
+}
+
+impl From<Dir4> for Dir8 {
+    fn from(dir: Dir4) -> Dir8 {
+        match dir {
+            Dir4::Up => Dir8::N,
+            Dir4::Right => Dir8::E,
+            Dir4::Down => Dir8::S,
+            Dir4::Left => Dir8::W,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn vector_arithmetic() {
+        let p = Point::new(2, -3);
+        assert_eq!(p + Dir4::Up.step() * 4, Point::new(2, -7));
+        assert_eq!(p - p, Point::ORIGIN);
+        assert_eq!(-p, Point::new(-2, 3));
+        assert_eq!(Point::new(5, -9).signum(), Point::new(1, -1));
+    }
+
+    #[test]
+    fn distances() {
+        let a = Point::new(1, 1);
+        let b = Point::new(-2, 5);
+        assert_eq!(a.manhattan(b), 7);
+        assert_eq!(a.chebyshev(b), 4);
+        assert_eq!(a.chebyshev(a + Dir8::SW.step()), 1);
+    }
+
+    #[test]
+    fn directions() {
+        for dir in Dir4::ALL {
+            assert_eq!(dir.to_string().parse::<Dir4>().unwrap(), dir);
+            assert_eq!(Dir8::from(dir).step(), dir.step());
+        }
+        assert!("X".parse::<Dir4>().is_err());
+        assert_eq!(
+            Dir8::ALL.iter().map(|dir| dir.step()).sum::<Point>(),
+            Point::ORIGIN
+        );
+    }
+}
@@ -0,0 +1,352 @@
+use crate::lines;
+use crate::Dir8;
+use crate::ParseError;
+use crate::Point;
+use std::fmt;
+use std::ops::Index;
+use std::ops::IndexMut;
+
+/// A rectangular grid stored row by row, indexed by `(x, y)` from the top left.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    pub fn new(width: usize, height: usize, fill: T) -> Self
+    where
+        T: Clone,
+    {
+        Self {
+            width,
+            height,
+            cells: vec![fill; width * height],
+        }
+    }
+
+    /// Builds a grid from its rows, or `None` if they differ in length.
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Option<Self> {
+        let width = rows.first().map_or(0, Vec::len);
+        let height = rows.len();
+        if rows.iter().any(|row| row.len() != width) {
+            return None;
+        }
+        Some(Self {
+            width,
+            height,
+            cells: rows.into_iter().flatten().collect(),
+        })
+    }
+
+    /// Parses a character map, one row per line, converting each character
+    /// with `cell`. Characters it rejects are reported with `message`.
+    pub fn parse(
+        input: &str,
+        message: &str,
+        mut cell: impl FnMut(char) -> Option<T>,
+    ) -> Result<Self, ParseError> {
+        let mut grid = Self {
+            width: 0,
+            height: 0,
+            cells: Vec::new(),
+        };
+        for line in lines(input) {
+            let start = grid.cells.len();
+            for (pos, c) in line.text.char_indices() {
+                match cell(c) {
+                    Some(val) => grid.cells.push(val),
+                    None => {
+                        let token = &line.text[pos..pos + c.len_utf8()];
+                        return Err(line.error_at(token, message));
+                    }
+                }
+            }
+            let width = grid.cells.len() - start;
+            if grid.height == 0 {
+                grid.width = width;
+            } else if width != grid.width {
+                return Err(line.error(format!("expected {} cells in this row", grid.width)));
+            }
+            grid.height += 1;
+        }
+        if grid.cells.is_empty() {
+            return Err(ParseError::eof(input, "missing grid"));
+        }
+        Ok(grid)
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, p: Point) -> bool {
+        (0..self.width as i64).contains(&p.x) && (0..self.height as i64).contains(&p.y)
+    }
+
+    /// The cell at a signed position, if it's inside the grid.
+    pub fn at(&self, p: Point) -> Option<&T> {
+        self.contains(p)
+            .then(|| &self[(p.x as usize, p.y as usize)])
+    }
+
+    pub fn at_mut(&mut self, p: Point) -> Option<&mut T> {
+        if self.contains(p) {
+            Some(&mut self[(p.x as usize, p.y as usize)])
+        } else {
+            None
+        }
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> Option<&T> {
+        (x < self.width && y < self.height).then(|| &self.cells[y * self.width + x])
+    }
+
+    pub fn get_mut(&mut self, x: usize, y: usize) -> Option<&mut T> {
+        (x < self.width && y < self.height).then(|| &mut self.cells[y * self.width + x])
+    }
+
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        // `chunks` rejects a zero width, which an empty grid has
+        self.cells.chunks(self.width.max(1))
+    }
+
+    pub fn col(&self, x: usize) -> impl Iterator<Item = &T> {
+        assert!(
+            x < self.width,
+            "column {x} outside a grid {} wide",
+            self.width
+        );
+        self.cells.iter().skip(x).step_by(self.width)
+    }
+
+    pub fn cols(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(|x| self.col(x))
+    }
+
+    /// Every diagonal running down and to the right, starting from the
+    /// bottom left corner.
+    pub fn diagonals(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        let (width, height) = self.extent();
+        let starts = (0..height)
+            .rev()
+            .map(|y| (0, y))
+            .chain((1..width).map(|x| (x, 0)));
+        starts.map(|(x, y)| self.cells_from(x, y, Dir8::SE).map(|(_, cell)| cell))
+    }
+
+    /// Every diagonal running down and to the left, starting from the top
+    /// left corner.
+    pub fn anti_diagonals(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        let (width, height) = self.extent();
+        let starts = (0..width)
+            .map(|x| (x, 0))
+            .chain((1..height).map(move |y| (width - 1, y)));
+        starts.map(|(x, y)| self.cells_from(x, y, Dir8::SW).map(|(_, cell)| cell))
+    }
+
+    /// The width and height, both zero if either is, so a grid with no
+    /// cells has no lines through it either.
+    fn extent(&self) -> (usize, usize) {
+        if self.cells.is_empty() {
+            (0, 0)
+        } else {
+            (self.width, self.height)
+        }
+    }
+
+    /// The cells met walking from `(x, y)` in `dir` until leaving the grid,
+    /// not including `(x, y)` itself.
+    pub fn ray(
+        &self,
+        x: usize,
+        y: usize,
+        dir: impl Into<Point>,
+    ) -> impl Iterator<Item = ((usize, usize), &T)> {
+        self.cells_from(x, y, dir).skip(1)
+    }
+
+    fn cells_from(
+        &self,
+        x: usize,
+        y: usize,
+        dir: impl Into<Point>,
+    ) -> impl Iterator<Item = ((usize, usize), &T)> {
+        let step = dir.into();
+        assert!(step != Point::ORIGIN, "a ray needs a direction");
+        let start = Point::new(x as i64, y as i64);
+        (0..)
+            .map(move |i| start + step * i)
+            .take_while(|&p| self.contains(p))
+            .map(|p| {
+                let pos = (p.x as usize, p.y as usize);
+                (pos, &self[pos])
+            })
+    }
+
+    /// The in-bounds positions one step from `(x, y)` in each of `dirs`,
+    /// usually `Dir4::ALL` or `Dir8::ALL`.
+    pub fn neighbours<D: Into<Point>>(
+        &self,
+        x: usize,
+        y: usize,
+        dirs: impl IntoIterator<Item = D>,
+    ) -> impl Iterator<Item = (usize, usize)> + '_ {
+        let start = Point::new(x as i64, y as i64);
+        dirs.into_iter()
+            .map(move |dir| start + dir.into())
+            .filter(|&p| self.contains(p))
+            .map(|p| (p.x as usize, p.y as usize))
+            // Collected so the directions aren't tied to the grid's lifetime
+            .collect::<Vec<_>>()
+            .into_iter()
+    }
+
+    /// Every position, row by row.
+    pub fn positions(&self) -> impl Iterator<Item = (usize, usize)> {
+        let width = self.width;
+        (0..self.height).flat_map(move |y| (0..width).map(move |x| (x, y)))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = ((usize, usize), &T)> {
+        self.positions().zip(&self.cells)
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+
+    /// A bigger grid with `fill` added around the edges, with this grid's
+    /// cells starting at `(left, top)`.
+    pub fn padded(&self, left: usize, top: usize, right: usize, bottom: usize, fill: T) -> Self
+    where
+        T: Clone,
+    {
+        let mut grid = Self::new(left + self.width + right, top + self.height + bottom, fill);
+        for ((x, y), cell) in self.iter() {
+            grid[(x + left, y + top)] = cell.clone();
+        }
+        grid
+    }
+}
+
+impl<T> Index<(usize, usize)> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, (x, y): (usize, usize)) -> &T {
+        self.get(x, y)
+            .unwrap_or_else(|| panic!("({x}, {y}) outside a {}x{} grid", self.width, self.height))
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Grid<T> {
+    fn index_mut(&mut self, (x, y): (usize, usize)) -> &mut T {
+        let (width, height) = (self.width, self.height);
+        self.get_mut(x, y)
+            .unwrap_or_else(|| panic!("({x}, {y}) outside a {width}x{height} grid"))
+    }
+}
+
+/// Writes one line per row, with no trailing newline.
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (y, row) in self.rows().enumerate() {
+            if y > 0 {
+                writeln!(f)?;
+            }
+            for cell in row {
+                write!(f, "{cell}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Dir4;
+
+    fn digits() -> Grid<u32> {
+        Grid::parse("123\n456\n", "expected a digit", |c| c.to_digit(10)).unwrap()
+    }
+
+    #[test]
+    fn parses_and_displays() {
+        let grid = digits();
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid[(2, 1)], 6);
+        assert_eq!(grid.get(3, 0), None);
+        assert_eq!(grid.to_string(), "123\n456");
+    }
+
+    #[test]
+    fn reports_bad_maps() {
+        let err = Grid::parse("12\n3x\n", "expected a digit", |c| c.to_digit(10)).unwrap_err();
+        assert_eq!((err.line, err.column), (2, 2));
+        let err = Grid::parse("12\n345\n", "expected a digit", |c| c.to_digit(10)).unwrap_err();
+        assert_eq!(err.line, 2);
+        assert!(Grid::parse("", "expected a digit", |c| c.to_digit(10)).is_err());
+    }
+
+    #[test]
+    fn iterates_lines() {
+        let grid = digits();
+        let cols = grid.cols().map(|col| col.copied().collect::<Vec<_>>());
+        assert_eq!(cols.collect::<Vec<_>>(), [[1, 4], [2, 5], [3, 6]]);
+        let diagonals = grid
+            .diagonals()
+            .map(|diagonal| diagonal.copied().collect::<Vec<_>>());
+        assert_eq!(
+            diagonals.collect::<Vec<_>>(),
+            [vec![4], vec![1, 5], vec![2, 6], vec![3]]
+        );
+        let anti = grid
+            .anti_diagonals()
+            .map(|diagonal| diagonal.copied().collect::<Vec<_>>());
+        assert_eq!(
+            anti.collect::<Vec<_>>(),
+            [vec![1], vec![2, 4], vec![3, 5], vec![6]]
+        );
+    }
+
+    #[test]
+    fn empty_grids_have_no_diagonals() {
+        for grid in [Grid::new(0, 3, 0), Grid::new(3, 0, 0), Grid::new(0, 0, 0)] {
+            assert_eq!(grid.diagonals().count(), 0);
+            assert_eq!(grid.anti_diagonals().count(), 0);
+        }
+    }
+
+    #[test]
+    fn rays_and_neighbours() {
+        let grid = digits();
+        let ray = grid.ray(0, 0, Dir4::Right).map(|(_, &cell)| cell);
+        assert_eq!(ray.collect::<Vec<_>>(), [2, 3]);
+        assert_eq!(grid.ray(0, 0, Dir4::Left).count(), 0);
+        assert_eq!(grid.at(Point::new(-1, 0)), None);
+        assert_eq!(grid.at(Point::new(1, 1)), Some(&5));
+        let mut around = grid.neighbours(0, 1, Dir8::ALL).collect::<Vec<_>>();
+        around.sort();
+        assert_eq!(around, [(0, 0), (1, 0), (1, 1)]);
+    }
+
+    #[test]
+    fn pads() {
+        let grid = digits().padded(1, 0, 0, 1, 0);
+        assert_eq!(grid.to_string(), "0123\n0456\n0000");
+    }
+}
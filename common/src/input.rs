@@ -0,0 +1,106 @@
+use anyhow::Context;
+use anyhow::Result;
+use clap::Args;
+use std::convert::Infallible;
+use std::fmt;
+use std::fs::File;
+use std::io;
+use std::io::BufRead;
+use std::io::BufReader;
+use std::io::Read;
+use std::path::PathBuf;
+use std::str::FromStr;
+
+/// Where a day's puzzle input comes from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InputSource {
+    Path(PathBuf),
+    Stdin,
+    Inline(String),
+}
+
+impl InputSource {
+    /// Reads the whole input into a string.
+    pub fn read(&self) -> Result<String> {
+        let mut input = String::new();
+        match self {
+            InputSource::Path(path) => {
+                let mut f =
+                    File::open(path).with_context(|| format!("opening {}", path.display()))?;
+                f.read_to_string(&mut input)
+                    .with_context(|| format!("reading {}", path.display()))?;
+            }
+            InputSource::Stdin => {
+                io::stdin()
+                    .read_to_string(&mut input)
+                    .context("reading stdin")?;
+            }
+            InputSource::Inline(s) => input.push_str(s),
+        }
+        Ok(input)
+    }
+
+    /// Opens the input to be read bit by bit instead of all at once.
+    pub fn open(&self) -> Result<Box<dyn BufRead + '_>> {
+        Ok(match self {
+            InputSource::Path(path) => Box::new(BufReader::new(
+                File::open(path).with_context(|| format!("opening {}", path.display()))?,
+            )),
+            InputSource::Stdin => Box::new(io::stdin().lock()),
+            InputSource::Inline(s) => Box::new(s.as_bytes()),
+        })
+    }
+}
+
+/// `-` means stdin, anything else is a path.
+impl FromStr for InputSource {
+    type Err = Infallible;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s {
+            "-" => InputSource::Stdin,
+            path => InputSource::Path(path.into()),
+        })
+    }
+}
+
+impl fmt::Display for InputSource {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            InputSource::Path(path) => write!(f, "{}", path.display()),
+            InputSource::Stdin => write!(f, "<stdin>"),
+            InputSource::Inline(_) => write!(f, "<input-str>"),
+        }
+    }
+}
+
+/// The input selection flags, shared by every binary that runs a day.
+#[derive(Debug, Clone, Args)]
+pub struct InputOpts {
+    #[clap(value_name = "INPUT", help = "Path to input file, or `-` for stdin")]
+    pub input: Option<InputSource>,
+    #[clap(
+        id = "input_path",
+        long = "input",
+        value_name = "PATH",
+        conflicts_with = "input",
+        help = "Path to input file, or `-` for stdin"
+    )]
+    pub input_path: Option<InputSource>,
+    #[clap(
+        long,
+        value_name = "INPUT",
+        conflicts_with_all = ["input", "input_path"],
+        help = "Puzzle input given inline"
+    )]
+    pub input_str: Option<String>,
+}
+
+impl InputOpts {
+    /// The selected source, if any flag was given.
+    pub fn source(&self) -> Option<InputSource> {
+        self.input
+            .clone()
+            .or_else(|| self.input_path.clone())
+            .or_else(|| self.input_str.clone().map(InputSource::Inline))
+    }
+}
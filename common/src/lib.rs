@@ -0,0 +1,249 @@
+use anyhow::anyhow;
+use anyhow::Result;
+use clap::Parser;
+use serde::Serialize;
+use std::io;
+use std::io::Write;
+use std::path::Path;
+use tracing::debug;
+use tracing::info_span;
+
+mod animate;
+mod answer;
+mod gen;
+mod geom;
+mod grid;
+mod input;
+mod log;
+mod outline;
+mod parse;
+mod render;
+mod repl;
+mod solution;
+mod stream;
+
+pub use animate::play;
+pub use animate::Animate;
+pub use animate::Animation;
+pub use answer::load_expected;
+pub use answer::Answer;
+pub use answer::Expected;
+pub use gen::Generator;
+pub use gen::Rng;
+pub use geom::Dir4;
+pub use geom::Dir8;
+pub use geom::Point;
+pub use grid::Grid;
+pub use input::InputOpts;
+pub use input::InputSource;
+pub use log::LogOpts;
+pub use outline::Node;
+pub use outline::Outline;
+pub use parse::in_file;
+pub use parse::lines;
+pub use parse::Line;
+pub use parse::ParseError;
+pub use render::write_png;
+pub use render::write_ppm;
+pub use render::Frame;
+pub use render::Frames;
+pub use render::GifWriter;
+pub use render::Render;
+pub use render::Rgb;
+pub use repl::arg;
+pub use repl::unknown;
+pub use repl::Repl;
+pub use repl::Session;
+pub use solution::Format;
+pub use solution::Part;
+pub use solution::PartReport;
+pub use solution::Report;
+pub use solution::RunOpts;
+pub use solution::Solution;
+pub use stream::LineReader;
+pub use stream::Stream;
+
+/// Command line options shared by every day's binary.
+#[derive(Debug, Parser)]
+pub struct Opts {
+    #[clap(flatten)]
+    pub input: InputOpts,
+    #[clap(flatten)]
+    pub run: RunOpts,
+    #[clap(flatten)]
+    pub log: LogOpts,
+}
+
+/// A registered puzzle solver, exported by each day's crate as `DAY`.
+#[derive(Debug, Clone, Copy)]
+pub struct Day {
+    pub number: u32,
+    pub name: &'static str,
+    pub solve: fn(&str, Part) -> Result<Report, ParseError>,
+    /// The solver's [`Solution::VERSION`].
+    pub version: &'static str,
+    pub generator: fn(&mut Rng, usize, &mut dyn Write) -> io::Result<()>,
+    /// What one unit of the generator's size is.
+    pub unit: &'static str,
+    /// The views `render` can draw, empty for days with nothing to show.
+    pub views: &'static [&'static str],
+    pub render: Option<render::RenderFn>,
+    /// Plays the day back in the terminal, see [`Animate`].
+    pub animate: Option<animate::AnimateFn>,
+    /// Solves the input as it's read, see [`Stream`].
+    pub stream: Option<stream::StreamFn>,
+    /// The commands `repl` understands, empty for days without one.
+    pub commands: &'static [(&'static str, &'static str)],
+    /// Starts exploring a parsed input, see [`Repl`].
+    pub repl: Option<repl::ReplFn>,
+    /// Lays the parsed input out as a tree, see [`Outline`].
+    pub outline: Option<outline::OutlineFn>,
+}
+
+impl Day {
+    pub const fn new<S: Solution + Generator>(number: u32, name: &'static str) -> Self {
+        Self {
+            number,
+            name,
+            solve: solution::solve::<S>,
+            version: S::VERSION,
+            generator: S::generate,
+            unit: S::UNIT,
+            views: &[],
+            render: None,
+            animate: None,
+            stream: None,
+            commands: &[],
+            repl: None,
+            outline: None,
+        }
+    }
+
+    /// Registers the day's visualisations, see [`Render`].
+    pub const fn with_render<S: Render>(self) -> Self {
+        Self {
+            views: S::VIEWS,
+            render: Some(render::render::<S>),
+            ..self
+        }
+    }
+
+    /// Lets the day be watched with `--animate`, see [`Animate`].
+    pub const fn with_animate<S: Animate>(self) -> Self {
+        Self {
+            animate: Some(animate::animate::<S>),
+            ..self
+        }
+    }
+
+    /// Lets the day solve inputs as they're read, see [`Stream`].
+    pub const fn with_stream<S: Stream>(self) -> Self {
+        Self {
+            stream: Some(stream::stream::<S>),
+            ..self
+        }
+    }
+
+    /// Lets the day's parsed input be explored with `aoc repl`, see [`Repl`].
+    pub const fn with_repl<S: Repl>(self) -> Self {
+        Self {
+            commands: S::COMMANDS,
+            repl: Some(repl::session::<S>),
+            ..self
+        }
+    }
+
+    /// Lets `aoc serve` show the day's input as a tree, see [`Outline`].
+    pub const fn with_outline<S: Outline>(self) -> Self {
+        Self {
+            outline: Some(outline::outline::<S>),
+            ..self
+        }
+    }
+
+    /// Reads the input from `source`, solves it and prints the report.
+    pub fn run(&self, source: &InputSource, opts: &RunOpts) -> Result<()> {
+        let _span = info_span!("day", day = self.number).entered();
+        if opts.animate {
+            let animate = self
+                .animate
+                .ok_or_else(|| anyhow!("day {} has no animation", self.number))?;
+            let raw = source.read()?;
+            debug!(input = %source, bytes = raw.len(), "read input");
+            return animate(&raw, opts.part, opts.fps).map_err(|err| in_file(err, source));
+        }
+        let report = self.solve_source(source, opts.part, opts.stream)?;
+        self.print(source, &report, opts)
+    }
+
+    /// Reads `source` and solves it. With `stream`, a day that can solves it
+    /// a line at a time instead; that always works out both parts in the one
+    /// pass and times all of it as parsing, so it's only done when asked.
+    pub fn solve_source(&self, source: &InputSource, part: Part, stream: bool) -> Result<Report> {
+        match self.stream.filter(|_| stream) {
+            Some(stream) => {
+                debug!(input = %source, "streaming input");
+                stream(&mut source.open()?, part).map_err(|err| in_file(err, source))
+            }
+            None => {
+                let raw = source.read()?;
+                debug!(input = %source, bytes = raw.len(), "read input");
+                Ok((self.solve)(&raw, part).map_err(|err| err.with_file(source))?)
+            }
+        }
+    }
+
+    /// Prints a report for `source` the way `opts` asks for.
+    pub fn print(&self, source: &InputSource, report: &Report, opts: &RunOpts) -> Result<()> {
+        match opts.format {
+            Format::Text => report.print_text(opts.time),
+            Format::Json => {
+                let run = JsonRun {
+                    day: self.number,
+                    name: self.name,
+                    input: source.to_string(),
+                    report,
+                };
+                println!("{}", serde_json::to_string(&run)?);
+            }
+        }
+        Ok(())
+    }
+
+    /// The checked-in puzzle input, relative to the workspace root.
+    pub fn default_input(&self) -> InputSource {
+        InputSource::Path(Path::new(self.name).join("input"))
+    }
+
+    /// A generated input of `size` units, always the same for a given seed.
+    pub fn generate(&self, seed: u64, size: usize) -> String {
+        let mut out = Vec::new();
+        (self.generator)(&mut Rng::new(seed), size, &mut out).expect("writing to a Vec");
+        String::from_utf8(out).expect("generators emit ASCII")
+    }
+
+    /// The known answers for the day's checked-in inputs.
+    pub fn expected(&self) -> Result<Vec<Expected>> {
+        load_expected(Path::new(self.name))
+    }
+}
+
+#[derive(Serialize)]
+struct JsonRun<'a> {
+    day: u32,
+    name: &'a str,
+    input: String,
+    #[serde(flatten)]
+    report: &'a Report,
+}
+
+/// Entry point for a day's standalone binary.
+pub fn main(day: &Day) -> Result<()> {
+    let opts = Opts::parse();
+    opts.log.init()?;
+    let source = opts
+        .input
+        .source()
+        .ok_or_else(|| anyhow!("no input given, pass a path, `-` or --input-str"))?;
+    day.run(&source, &opts.run)
+}
@@ -0,0 +1,118 @@
+use anyhow::anyhow;
+use anyhow::Context;
+use anyhow::Result;
+use clap::ArgAction;
+use clap::Args;
+use std::fs::File;
+use std::io;
+use std::io::IsTerminal;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use tracing::level_filters::LevelFilter;
+use tracing_subscriber::fmt;
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::util::SubscriberInitExt;
+use tracing_subscriber::EnvFilter;
+use tracing_subscriber::Layer;
+
+/// Logging flags shared by every binary. Logs go to stderr, so stdout only
+/// ever holds answers.
+#[derive(Debug, Clone, Args)]
+pub struct LogOpts {
+    #[clap(
+        short,
+        long,
+        global = true,
+        action = ArgAction::Count,
+        help = "Log more: -v for progress, -vv for puzzle state, -vvv for every step"
+    )]
+    pub verbose: u8,
+    #[clap(
+        short,
+        long,
+        global = true,
+        conflicts_with = "verbose",
+        help = "Only log errors"
+    )]
+    pub quiet: bool,
+    #[clap(
+        long,
+        global = true,
+        value_name = "PATH",
+        help = "Also write the logs to PATH, one JSON object per line"
+    )]
+    pub log_json: Option<PathBuf>,
+}
+
+impl LogOpts {
+    pub fn level(&self) -> LevelFilter {
+        match (self.quiet, self.verbose) {
+            (true, _) => LevelFilter::ERROR,
+            (false, 0) => LevelFilter::WARN,
+            (false, 1) => LevelFilter::INFO,
+            (false, 2) => LevelFilter::DEBUG,
+            (false, _) => LevelFilter::TRACE,
+        }
+    }
+
+    /// Installs the global subscriber. `RUST_LOG`, when set, takes over from
+    /// the flags to pick what gets logged.
+    pub fn init(&self) -> Result<()> {
+        let filter = || {
+            EnvFilter::builder()
+                .with_default_directive(self.level().into())
+                .from_env_lossy()
+        };
+        let stderr = fmt::layer()
+            .with_writer(io::stderr)
+            .with_ansi(io::stderr().is_terminal())
+            .with_target(false)
+            .with_filter(filter());
+        let json = match &self.log_json {
+            Some(path) => {
+                let file =
+                    File::create(path).with_context(|| format!("creating {}", path.display()))?;
+                Some(
+                    fmt::layer()
+                        .json()
+                        .with_writer(Mutex::new(file))
+                        .with_filter(filter()),
+                )
+            }
+            None => None,
+        };
+        tracing_subscriber::registry()
+            .with(stderr)
+            .with(json)
+            .try_init()
+            .map_err(|err| anyhow!("setting up logging: {err}"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::Parser;
+
+    #[derive(Parser)]
+    struct Opts {
+        #[clap(flatten)]
+        log: LogOpts,
+    }
+
+    fn level(args: &[&str]) -> LevelFilter {
+        Opts::parse_from(std::iter::once("test").chain(args.iter().copied()))
+            .log
+            .level()
+    }
+
+    #[test]
+    fn verbosity_flags_pick_the_level() {
+        assert_eq!(level(&[]), LevelFilter::WARN);
+        assert_eq!(level(&["-v"]), LevelFilter::INFO);
+        assert_eq!(level(&["-vv"]), LevelFilter::DEBUG);
+        assert_eq!(level(&["-v", "-v", "-v", "-v"]), LevelFilter::TRACE);
+        assert_eq!(level(&["--quiet"]), LevelFilter::ERROR);
+        assert!(Opts::try_parse_from(["test", "-q", "-v"]).is_err());
+    }
+}
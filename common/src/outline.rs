@@ -0,0 +1,31 @@
+use crate::ParseError;
+use crate::Solution;
+
+/// One entry in a day's hierarchy and everything under it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Node {
+    pub label: String,
+    pub children: Vec<Node>,
+}
+
+impl Node {
+    pub fn new(label: impl Into<String>) -> Self {
+        Self {
+            label: label.into(),
+            children: Vec::new(),
+        }
+    }
+}
+
+/// A day whose parsed input is a tree worth showing, like seven's
+/// filesystem.
+pub trait Outline: Solution {
+    fn outline(input: &Self::Input) -> Node;
+}
+
+/// How a [`crate::Day`] lays out its input as a tree.
+pub type OutlineFn = fn(&str) -> Result<Node, ParseError>;
+
+pub(crate) fn outline<S: Outline>(input: &str) -> Result<Node, ParseError> {
+    Ok(S::outline(&S::parse(input)?))
+}
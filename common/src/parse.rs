@@ -0,0 +1,124 @@
+use std::error::Error;
+use std::fmt;
+use std::fmt::Display;
+use std::str::FromStr;
+
+/// A parse failure pinned to a position in the puzzle input.
+///
+/// `line` and `column` are 1-based, and `column` counts characters rather
+/// than bytes. `len` is the width of the offending token, used to draw the
+/// caret underline.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub file: Option<String>,
+    pub line: usize,
+    pub column: usize,
+    pub len: usize,
+    pub text: String,
+    pub message: String,
+}
+
+impl ParseError {
+    pub fn new(line: usize, column: usize, len: usize, text: &str, message: impl Display) -> Self {
+        Self {
+            file: None,
+            line,
+            column,
+            len,
+            text: text.to_string(),
+            message: message.to_string(),
+        }
+    }
+
+    /// An error past the last line, for input that ends too early.
+    pub fn eof(input: &str, message: impl Display) -> Self {
+        Self::new(input.lines().count() + 1, 1, 0, "", message)
+    }
+
+    pub fn with_file(mut self, file: impl Display) -> Self {
+        self.file = Some(file.to_string());
+        self
+    }
+}
+
+impl Error for ParseError {}
+
+/// Names `file` in `err` if it's a [`ParseError`], for stages that can fail
+/// in other ways too.
+pub fn in_file(err: anyhow::Error, file: impl Display) -> anyhow::Error {
+    match err.downcast::<ParseError>() {
+        Ok(err) => err.with_file(file).into(),
+        Err(err) => err,
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let file = self.file.as_deref().unwrap_or("<input>");
+        let gutter = " ".repeat(self.line.to_string().len());
+        writeln!(f, "{}", self.message)?;
+        writeln!(f, "{gutter}--> {file}:{}:{}", self.line, self.column)?;
+        writeln!(f, "{gutter} |")?;
+        writeln!(f, "{} | {}", self.line, self.text)?;
+        write!(
+            f,
+            "{gutter} | {}{}",
+            " ".repeat(self.column - 1),
+            "^".repeat(self.len.max(1))
+        )
+    }
+}
+
+/// One line of puzzle input, remembering where it came from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Line<'a> {
+    pub number: usize,
+    pub text: &'a str,
+}
+
+impl<'a> Line<'a> {
+    /// An error underlining `token`, which must be a slice of this line.
+    /// Anything else underlines the whole line.
+    pub fn error_at(&self, token: &str, message: impl Display) -> ParseError {
+        let (start, len) = match self.offset_of(token) {
+            Some(offset) => (offset, token.chars().count()),
+            None => (0, self.text.chars().count()),
+        };
+        let column = self.text[..start].chars().count() + 1;
+        ParseError::new(self.number, column, len, self.text, message)
+    }
+
+    /// An error underlining the whole line.
+    pub fn error(&self, message: impl Display) -> ParseError {
+        self.error_at(self.text, message)
+    }
+
+    /// Parses `token`, a slice of this line, reporting failures against it.
+    pub fn parse<T>(&self, token: &str) -> Result<T, ParseError>
+    where
+        T: FromStr,
+        <T as FromStr>::Err: Display,
+    {
+        T::from_str(token).map_err(|err| self.error_at(token, err))
+    }
+
+    /// Splits on single spaces, as the puzzle inputs do.
+    pub fn words(&self) -> Vec<&'a str> {
+        self.text.split(' ').collect()
+    }
+
+    fn offset_of(&self, token: &str) -> Option<usize> {
+        let start = self.text.as_ptr() as usize;
+        let token_start = token.as_ptr() as usize;
+        let offset = token_start.checked_sub(start)?;
+        (offset + token.len() <= self.text.len()).then_some(offset)
+    }
+}
+
+/// Numbers the lines of `input` from 1.
+pub fn lines(input: &str) -> impl Iterator<Item = Line<'_>> {
+    input.lines().enumerate().map(|(i, text)| Line {
+        number: i + 1,
+        text,
+    })
+}
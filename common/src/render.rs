@@ -0,0 +1,290 @@
+use crate::Grid;
+use crate::Solution;
+use anyhow::Result;
+use std::collections::HashMap;
+use std::io;
+use std::io::Write;
+
+/// One frame of a visualisation, a colour per grid cell.
+pub type Frame = Grid<Rgb>;
+
+/// Where a day hands each frame it draws.
+pub type Frames<'a> = &'a mut dyn FnMut(&Frame) -> io::Result<()>;
+
+/// A day that can draw its puzzle state as a sequence of frames.
+pub trait Render: Solution {
+    /// The views it can draw, the first being the default.
+    const VIEWS: &'static [&'static str];
+
+    /// Draws `view`, which is always one of [`Render::VIEWS`], handing each
+    /// frame to `frames` in order. Every frame of a view is the same size.
+    fn render(input: &Self::Input, view: &str, frames: Frames<'_>) -> io::Result<()>;
+}
+
+/// How a [`crate::Day`] draws one of its views.
+pub type RenderFn = fn(&str, &str, Frames<'_>) -> Result<()>;
+
+pub(crate) fn render<S: Render>(input: &str, view: &str, frames: Frames<'_>) -> Result<()> {
+    let input = S::parse(input)?;
+    S::render(&input, view, frames)?;
+    Ok(())
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Rgb(pub u8, pub u8, pub u8);
+
+impl Rgb {
+    pub const BLACK: Rgb = Rgb(0, 0, 0);
+    pub const WHITE: Rgb = Rgb(255, 255, 255);
+
+    /// The colour `t` of the way from `self` to `other`, `t` being 0 to 1.
+    pub fn mix(self, other: Rgb, t: f64) -> Rgb {
+        let t = t.clamp(0.0, 1.0);
+        let channel = |a: u8, b: u8| (a as f64 + (b as f64 - a as f64) * t).round() as u8;
+        Rgb(
+            channel(self.0, other.0),
+            channel(self.1, other.1),
+            channel(self.2, other.2),
+        )
+    }
+
+    /// A heatmap colour running black, red, yellow, white as `t` goes 0 to 1.
+    pub fn heat(t: f64) -> Rgb {
+        const STOPS: [Rgb; 4] = [Rgb::BLACK, Rgb(200, 30, 0), Rgb(255, 210, 0), Rgb::WHITE];
+        let t = t.clamp(0.0, 1.0) * (STOPS.len() - 1) as f64;
+        let stop = (t as usize).min(STOPS.len() - 2);
+        STOPS[stop].mix(STOPS[stop + 1], t - stop as f64)
+    }
+}
+
+/// Packs the grid's pixels row by row, each cell drawn as a `scale` pixel
+/// square of the bytes `pixel` gives it.
+fn scaled<T: Copy, const N: usize>(
+    grid: &Grid<T>,
+    scale: usize,
+    pixel: impl Fn(T) -> [u8; N],
+) -> Vec<u8> {
+    let mut out = Vec::with_capacity(grid.width() * grid.height() * scale * scale * N);
+    for row in grid.rows() {
+        for _ in 0..scale {
+            for &cell in row {
+                let bytes = pixel(cell);
+                for _ in 0..scale {
+                    out.extend_from_slice(&bytes);
+                }
+            }
+        }
+    }
+    out
+}
+
+/// The frame as packed RGB bytes.
+fn pixels(frame: &Frame, scale: usize) -> Vec<u8> {
+    scaled(frame, scale, |Rgb(r, g, b)| [r, g, b])
+}
+
+/// Writes the frame as a binary PPM image.
+pub fn write_ppm(frame: &Frame, scale: usize, out: &mut dyn Write) -> io::Result<()> {
+    let (width, height) = (frame.width() * scale, frame.height() * scale);
+    write!(out, "P6\n{width} {height}\n255\n")?;
+    out.write_all(&pixels(frame, scale))
+}
+
+/// Writes the frame as a PNG image.
+pub fn write_png(frame: &Frame, scale: usize, out: &mut dyn Write) -> io::Result<()> {
+    let (width, height) = (frame.width() * scale, frame.height() * scale);
+    let mut encoder = png::Encoder::new(out, width as u32, height as u32);
+    encoder.set_color(png::ColorType::Rgb);
+    encoder.set_depth(png::BitDepth::Eight);
+    let mut writer = encoder.write_header().map_err(io::Error::other)?;
+    writer
+        .write_image_data(&pixels(frame, scale))
+        .map_err(io::Error::other)?;
+    writer.finish().map_err(io::Error::other)
+}
+
+/// Writes frames one at a time into a looping animated GIF.
+pub struct GifWriter<W: Write> {
+    encoder: gif::Encoder<W>,
+    width: usize,
+    height: usize,
+    scale: usize,
+    /// Hundredths of a second each frame stays up.
+    delay: u16,
+    previous: Option<Frame>,
+}
+
+impl<W: Write> GifWriter<W> {
+    /// Starts a GIF for frames of `width` by `height` cells.
+    pub fn new(out: W, width: usize, height: usize, scale: usize, fps: u16) -> io::Result<Self> {
+        let too_big = |cells: usize| {
+            u16::try_from(cells * scale).map_err(|_| {
+                io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!("{cells} cells at scale {scale} is too big for a GIF"),
+                )
+            })
+        };
+        let mut encoder = gif::Encoder::new(out, too_big(width)?, too_big(height)?, &[])
+            .map_err(io::Error::other)?;
+        encoder
+            .set_repeat(gif::Repeat::Infinite)
+            .map_err(io::Error::other)?;
+        Ok(Self {
+            encoder,
+            width,
+            height,
+            scale,
+            delay: (100 / fps.max(1)).max(1),
+            previous: None,
+        })
+    }
+
+    pub fn frame(&mut self, frame: &Frame) -> io::Result<()> {
+        if (frame.width(), frame.height()) != (self.width, self.height) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!(
+                    "a {}x{} frame doesn't fit a {}x{} GIF",
+                    frame.width(),
+                    frame.height(),
+                    self.width,
+                    self.height
+                ),
+            ));
+        }
+        // Only the box around the cells that changed is encoded, the rest of
+        // the image stays up from the frame before
+        let (left, top, right, bottom) = match &self.previous {
+            Some(previous) => changed(previous, frame).unwrap_or((0, 0, 0, 0)),
+            None => (0, 0, self.width - 1, self.height - 1),
+        };
+        let patch = Grid::from_rows(
+            (top..=bottom)
+                .map(|y| frame.row(y)[left..=right].to_vec())
+                .collect(),
+        )
+        .expect("rows are cut to the same width");
+        let (width, height) = (
+            (patch.width() * self.scale) as u16,
+            (patch.height() * self.scale) as u16,
+        );
+        let mut palette = HashMap::new();
+        for (_, &colour) in patch.iter() {
+            let next = palette.len();
+            palette.entry(colour).or_insert(next);
+        }
+        let mut gif_frame = if palette.len() <= 256 {
+            // Our frames rarely use many colours, so index them directly
+            // rather than paying for quantisation
+            let mut colours = vec![0; palette.len() * 3];
+            for (&Rgb(r, g, b), &index) in &palette {
+                colours[index * 3..index * 3 + 3].copy_from_slice(&[r, g, b]);
+            }
+            let indices = scaled(&patch, self.scale, |colour| [palette[&colour] as u8]);
+            gif::Frame::from_palette_pixels(width, height, indices, colours, None)
+        } else {
+            gif::Frame::from_rgb_speed(width, height, &pixels(&patch, self.scale), 10)
+        };
+        gif_frame.left = (left * self.scale) as u16;
+        gif_frame.top = (top * self.scale) as u16;
+        gif_frame.dispose = gif::DisposalMethod::Keep;
+        gif_frame.delay = self.delay;
+        self.previous = Some(frame.clone());
+        self.encoder
+            .write_frame(&gif_frame)
+            .map_err(io::Error::other)
+    }
+
+    /// Finishes the GIF and hands back the writer.
+    pub fn finish(self) -> io::Result<W> {
+        self.encoder.into_inner()
+    }
+}
+
+/// The left, top, right and bottom cells bounding every cell that differs
+/// between two frames of the same size, or `None` if they're identical.
+fn changed(before: &Frame, after: &Frame) -> Option<(usize, usize, usize, usize)> {
+    let mut bounds: Option<(usize, usize, usize, usize)> = None;
+    for ((x, y), colour) in after.iter() {
+        if before[(x, y)] != *colour {
+            let (left, top, right, bottom) = bounds.unwrap_or((x, y, x, y));
+            bounds = Some((left.min(x), top.min(y), right.max(x), bottom.max(y)));
+        }
+    }
+    bounds
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn checker() -> Frame {
+        Grid::from_rows(vec![
+            vec![Rgb::BLACK, Rgb::WHITE],
+            vec![Rgb::WHITE, Rgb(255, 0, 0)],
+        ])
+        .unwrap()
+    }
+
+    #[test]
+    fn ppm_scales_cells() {
+        let mut out = Vec::new();
+        write_ppm(&checker(), 2, &mut out).unwrap();
+        let header = b"P6\n4 4\n255\n";
+        assert_eq!(&out[..header.len()], header);
+        let pixels = &out[header.len()..];
+        assert_eq!(pixels.len(), 4 * 4 * 3);
+        // Second row of pixels still belongs to the first row of cells
+        assert_eq!(&pixels[12..18], &[0, 0, 0, 0, 0, 0]);
+        assert_eq!(&pixels[pixels.len() - 3..], &[255, 0, 0]);
+    }
+
+    #[test]
+    fn png_decodes_to_the_same_pixels() {
+        let mut out = Vec::new();
+        write_png(&checker(), 3, &mut out).unwrap();
+        let mut reader = png::Decoder::new(&out[..]).read_info().unwrap();
+        let mut buf = vec![0; reader.output_buffer_size()];
+        let info = reader.next_frame(&mut buf).unwrap();
+        assert_eq!((info.width, info.height), (6, 6));
+        assert_eq!(&buf[..info.buffer_size()], &pixels(&checker(), 3)[..]);
+    }
+
+    #[test]
+    fn gif_holds_every_frame() {
+        let mut gif = GifWriter::new(Vec::new(), 2, 2, 1, 10).unwrap();
+        gif.frame(&checker()).unwrap();
+        gif.frame(&Grid::new(2, 2, Rgb(0, 0, 255))).unwrap();
+        let mut last = Grid::new(2, 2, Rgb(0, 0, 255));
+        last[(1, 1)] = Rgb::WHITE;
+        gif.frame(&last).unwrap();
+        assert!(gif.frame(&Grid::new(3, 2, Rgb::BLACK)).is_err());
+        let out = gif.finish().unwrap();
+
+        let mut options = gif::DecodeOptions::new();
+        options.set_color_output(gif::ColorOutput::RGBA);
+        let mut decoder = options.read_info(&out[..]).unwrap();
+        let first = decoder.read_next_frame().unwrap().unwrap();
+        assert_eq!(first.delay, 10);
+        assert_eq!(&first.buffer[12..16], &[255, 0, 0, 255]);
+        let second = decoder.read_next_frame().unwrap().unwrap();
+        assert_eq!(&second.buffer[..4], &[0, 0, 255, 255]);
+        // Only the cell that changed is stored
+        let third = decoder.read_next_frame().unwrap().unwrap();
+        assert_eq!(
+            (third.left, third.top, third.width, third.height),
+            (1, 1, 1, 1)
+        );
+        assert_eq!(&third.buffer[..], &[255, 255, 255, 255]);
+        assert!(decoder.read_next_frame().unwrap().is_none());
+    }
+
+    #[test]
+    fn heat_runs_from_black_to_white() {
+        assert_eq!(Rgb::heat(0.0), Rgb::BLACK);
+        assert_eq!(Rgb::heat(1.0), Rgb::WHITE);
+        assert_eq!(Rgb::heat(2.0), Rgb::WHITE);
+        assert_eq!(Rgb::heat(1.0 / 3.0), Rgb(200, 30, 0));
+    }
+}
@@ -0,0 +1,48 @@
+use crate::ParseError;
+use crate::Solution;
+use anyhow::anyhow;
+use anyhow::Result;
+use std::fmt::Display;
+use std::str::FromStr;
+
+/// A parsed input being explored from `aoc repl`.
+pub trait Session {
+    /// Carries out `command` with its `args`, returning what to print.
+    fn run(&mut self, command: &str, args: &[&str]) -> Result<String>;
+}
+
+/// A day whose parsed input can be explored a command at a time.
+pub trait Repl: Solution {
+    /// Each command's usage and what it does, for `help`.
+    const COMMANDS: &'static [(&'static str, &'static str)];
+
+    fn session(input: Self::Input) -> Box<dyn Session>;
+}
+
+/// How a [`crate::Day`] starts a session on its input.
+pub type ReplFn = fn(&str) -> Result<Box<dyn Session>, ParseError>;
+
+pub(crate) fn session<S: Repl>(input: &str) -> Result<Box<dyn Session>, ParseError> {
+    Ok(S::session(S::parse(input)?))
+}
+
+/// Parses argument `index`, called `name` in errors, falling back to
+/// `default` when there's no such argument.
+pub fn arg<T>(args: &[&str], index: usize, name: &str, default: Option<T>) -> Result<T>
+where
+    T: FromStr,
+    <T as FromStr>::Err: Display,
+{
+    match (args.get(index), default) {
+        (Some(arg), _) => arg
+            .parse()
+            .map_err(|err| anyhow!("bad {name} {arg:?}: {err}")),
+        (None, Some(default)) => Ok(default),
+        (None, None) => Err(anyhow!("missing {name}")),
+    }
+}
+
+/// The error for a command a session doesn't know.
+pub fn unknown(command: &str) -> anyhow::Error {
+    anyhow!("unknown command {command:?}, try `help`")
+}
@@ -0,0 +1,171 @@
+use crate::Answer;
+use crate::ParseError;
+use clap::Args;
+use clap::ValueEnum;
+use serde::Serialize;
+use std::time::Duration;
+use std::time::Instant;
+use tracing::debug;
+use tracing::info;
+
+/// A day's puzzle, split into a parse stage and one stage per part.
+///
+/// Both parts borrow the parsed input, so it only has to be built once.
+pub trait Solution {
+    type Input;
+    type Output1: Into<Answer>;
+    type Output2: Into<Answer>;
+
+    /// Bumped whenever a change could alter the answers, so results cached
+    /// by `aoc run` for an older solver are no longer used.
+    const VERSION: &'static str = "1";
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError>;
+    fn part1(input: &Self::Input) -> Self::Output1;
+    fn part2(input: &Self::Input) -> Self::Output2;
+}
+
+/// Which parts of a puzzle to solve.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Part {
+    #[value(name = "1")]
+    One,
+    #[value(name = "2")]
+    Two,
+    Both,
+}
+
+impl Part {
+    pub fn includes(self, part: u8) -> bool {
+        matches!(
+            (self, part),
+            (Part::Both, _) | (Part::One, 1) | (Part::Two, 2)
+        )
+    }
+}
+
+/// How answers are written to stdout.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Format {
+    Text,
+    Json,
+}
+
+/// Options controlling how a day is run, shared by every binary.
+#[derive(Debug, Clone, Args)]
+pub struct RunOpts {
+    #[clap(long, value_enum, default_value_t = Part::Both, help = "Which part to solve")]
+    pub part: Part,
+    #[clap(long, help = "Report how long parsing and each part took")]
+    pub time: bool,
+    #[clap(long, value_enum, default_value_t = Format::Text, help = "Output format")]
+    pub format: Format,
+    #[clap(
+        long,
+        help = "Watch the puzzle play out in the terminal instead of printing answers"
+    )]
+    pub animate: bool,
+    #[clap(
+        long,
+        default_value_t = 10,
+        value_parser = clap::value_parser!(u32).range(1..=1000),
+        requires = "animate",
+        help = "Frames per second for --animate"
+    )]
+    pub fps: u32,
+    #[clap(
+        long,
+        conflicts_with_all = ["part", "time", "animate"],
+        help = "Solve while reading, holding one line at a time; both parts come out of the one pass"
+    )]
+    pub stream: bool,
+}
+
+/// The answers and stage timings from one run of a day.
+#[derive(Debug, Clone, Serialize)]
+pub struct Report {
+    #[serde(rename = "parse_ns", serialize_with = "as_nanos")]
+    pub parse: Duration,
+    pub parts: Vec<PartReport>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct PartReport {
+    pub part: u8,
+    #[serde(flatten)]
+    pub answer: Answer,
+    #[serde(rename = "time_ns", serialize_with = "as_nanos")]
+    pub time: Duration,
+    /// Read back from an earlier run rather than solved, so `time` is zero.
+    #[serde(skip_serializing_if = "is_false")]
+    pub cached: bool,
+}
+
+impl Report {
+    pub fn print_text(&self, time: bool) {
+        for part in &self.parts {
+            let answer = part.answer.to_string();
+            if answer.contains('\n') {
+                println!("Part {}:\n{answer}", part.part);
+            } else {
+                println!("Part {}: {answer}", part.part);
+            }
+        }
+        if time {
+            println!("Timings:");
+            println!("  {:<6} {:>12.3?}", "parse", self.parse);
+            for part in &self.parts {
+                let name = format!("part {}", part.part);
+                if part.cached {
+                    println!("  {name:<6} {:>12}", "cached");
+                } else {
+                    println!("  {name:<6} {:>12.3?}", part.time);
+                }
+            }
+        }
+    }
+}
+
+fn is_false(val: &bool) -> bool {
+    !val
+}
+
+fn as_nanos<S: serde::Serializer>(duration: &Duration, s: S) -> Result<S::Ok, S::Error> {
+    s.serialize_u128(duration.as_nanos())
+}
+
+/// Parses `input` and solves the selected parts, timing each stage.
+pub fn solve<S: Solution>(input: &str, part: Part) -> Result<Report, ParseError> {
+    let start = Instant::now();
+    let input = S::parse(input)?;
+    let mut report = Report {
+        parse: start.elapsed(),
+        parts: Vec::new(),
+    };
+    debug!(elapsed = ?report.parse, "parsed input");
+    if part.includes(1) {
+        let start = Instant::now();
+        let answer = S::part1(&input).into();
+        let time = start.elapsed();
+        info!(part = 1, %answer, elapsed = ?time, "solved");
+        report.parts.push(PartReport {
+            part: 1,
+            answer,
+            time,
+            cached: false,
+        });
+    }
+    if part.includes(2) {
+        let start = Instant::now();
+        let answer = S::part2(&input).into();
+        let time = start.elapsed();
+        info!(part = 2, %answer, elapsed = ?time, "solved");
+        report.parts.push(PartReport {
+            part: 2,
+            answer,
+            time,
+            cached: false,
+        });
+    }
+    Ok(report)
+}
@@ -0,0 +1,100 @@
+use crate::Answer;
+use crate::Line;
+use crate::ParseError;
+use crate::Part;
+use crate::PartReport;
+use crate::Report;
+use crate::Solution;
+use anyhow::Context;
+use anyhow::Result;
+use std::fmt::Display;
+use std::io::BufRead;
+use std::time::Instant;
+use tracing::debug;
+use tracing::info;
+
+/// Puzzle input read one line at a time into a reused buffer, so only the
+/// current line is ever held however long the input is.
+pub struct LineReader<'a> {
+    reader: &'a mut dyn BufRead,
+    buf: String,
+    number: usize,
+}
+
+impl<'a> LineReader<'a> {
+    pub fn new(reader: &'a mut dyn BufRead) -> Self {
+        Self {
+            reader,
+            buf: String::new(),
+            number: 0,
+        }
+    }
+
+    /// The next line, without its line ending, or `None` at the end.
+    pub fn next_line(&mut self) -> Result<Option<Line<'_>>> {
+        self.buf.clear();
+        let read = self
+            .reader
+            .read_line(&mut self.buf)
+            .with_context(|| format!("reading line {}", self.number + 1))?;
+        if read == 0 {
+            return Ok(None);
+        }
+        self.number += 1;
+        // The same endings `str::lines` strips
+        let text = match self.buf.strip_suffix('\n') {
+            Some(text) => text.strip_suffix('\r').unwrap_or(text),
+            None => &self.buf,
+        };
+        Ok(Some(Line {
+            number: self.number,
+            text,
+        }))
+    }
+
+    /// An error past the last line, like [`ParseError::eof`].
+    pub fn eof(&self, message: impl Display) -> ParseError {
+        ParseError::new(self.number + 1, 1, 0, "", message)
+    }
+}
+
+/// A day that can also be solved in one pass over a reader, working out both
+/// parts as the lines go by rather than parsing the whole input first.
+pub trait Stream: Solution {
+    fn stream(lines: &mut LineReader<'_>) -> Result<(Self::Output1, Self::Output2)>;
+}
+
+/// How a [`crate::Day`] solves its input straight from a reader.
+pub type StreamFn = fn(&mut dyn BufRead, Part) -> Result<Report>;
+
+/// Both parts come out of the one pass, so all of it is timed as parsing
+/// and each part's time is only what turning it into an answer takes.
+pub(crate) fn stream<S: Stream>(reader: &mut dyn BufRead, part: Part) -> Result<Report> {
+    let start = Instant::now();
+    let (one, two) = S::stream(&mut LineReader::new(reader))?;
+    let mut report = Report {
+        parse: start.elapsed(),
+        parts: Vec::new(),
+    };
+    debug!(elapsed = ?report.parse, "streamed input");
+    if part.includes(1) {
+        let start = Instant::now();
+        report.parts.push(finish(1, one.into(), start));
+    }
+    if part.includes(2) {
+        let start = Instant::now();
+        report.parts.push(finish(2, two.into(), start));
+    }
+    Ok(report)
+}
+
+fn finish(part: u8, answer: Answer, start: Instant) -> PartReport {
+    let time = start.elapsed();
+    info!(part, %answer, elapsed = ?time, "solved");
+    PartReport {
+        part,
+        answer,
+        time,
+        cached: false,
+    }
+}
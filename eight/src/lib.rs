@@ -0,0 +1,270 @@
+use anyhow::anyhow;
+use anyhow::Result;
+use aoc_common::arg;
+use aoc_common::unknown;
+use aoc_common::Day;
+use aoc_common::Dir4;
+use aoc_common::Frames;
+use aoc_common::Generator;
+use aoc_common::Grid;
+use aoc_common::ParseError;
+use aoc_common::Render;
+use aoc_common::Repl;
+use aoc_common::Rgb;
+use aoc_common::Rng;
+use aoc_common::Session;
+use aoc_common::Solution;
+use std::cmp::Reverse;
+use std::io;
+use std::io::Write;
+use tracing::debug;
+
+pub const DAY: Day = Day::new::<Eight>(8, "eight")
+    .with_render::<Eight>()
+    .with_repl::<Eight>();
+
+pub struct Eight;
+
+impl Solution for Eight {
+    type Input = Grid<u8>;
+    type Output1 = usize;
+    type Output2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        let grid = Grid::parse(input, "expected a tree height digit", |c| {
+            c.to_digit(10).map(|height| height as u8)
+        })?;
+        debug!(
+            width = grid.width(),
+            height = grid.height(),
+            "read tree grid"
+        );
+        Ok(grid)
+    }
+
+    fn part1(grid: &Self::Input) -> usize {
+        grid.iter()
+            .filter(|&((x, y), _)| is_visible(grid, x, y))
+            .count()
+    }
+
+    fn part2(grid: &Self::Input) -> usize {
+        grid.iter()
+            .map(|((x, y), _)| scenic_score(grid, x, y))
+            .max()
+            .unwrap_or(0)
+    }
+}
+
+impl Render for Eight {
+    const VIEWS: &'static [&'static str] = &["visible", "scenic"];
+
+    /// `visible` shades trees seen from outside the grid green and the rest
+    /// grey, lighter for taller trees. `scenic` is a heatmap of scenic scores.
+    fn render(grid: &Self::Input, view: &str, frames: Frames<'_>) -> io::Result<()> {
+        let frame = if view == "scenic" {
+            let scores = Grid::from_rows(
+                (0..grid.height())
+                    .map(|y| {
+                        (0..grid.width())
+                            .map(|x| scenic_score(grid, x, y))
+                            .collect()
+                    })
+                    .collect(),
+            )
+            .expect("rows are the grid's width");
+            // Scores span orders of magnitude, so a log scale shows more
+            let best = (scores.iter().map(|(_, &score)| score).max().unwrap_or(0) as f64).ln_1p();
+            scores.map(|&score| Rgb::heat((score as f64).ln_1p() / best.max(1.0)))
+        } else {
+            let mut frame = grid.map(|_| Rgb::BLACK);
+            for ((x, y), &height) in grid.iter() {
+                let (short, tall) = if is_visible(grid, x, y) {
+                    (Rgb(0, 70, 0), Rgb(120, 255, 90))
+                } else {
+                    (Rgb(40, 40, 40), Rgb(150, 150, 150))
+                };
+                frame[(x, y)] = short.mix(tall, height as f64 / 9.0);
+            }
+            frame
+        };
+        frames(&frame)
+    }
+}
+
+impl Repl for Eight {
+    const COMMANDS: &'static [(&'static str, &'static str)] = &[
+        (
+            "score ROW COL",
+            "the scenic score of the tree at ROW, COL, counting from 0",
+        ),
+        (
+            "visible ROW COL",
+            "whether that tree can be seen from outside",
+        ),
+        ("best", "where the tree with the best scenic score is"),
+    ];
+
+    fn session(grid: Grid<u8>) -> Box<dyn Session> {
+        Box::new(Forest(grid))
+    }
+}
+
+struct Forest(Grid<u8>);
+
+impl Forest {
+    /// The `(x, y)` of the tree the args name by row and column.
+    fn tree(&self, args: &[&str]) -> Result<(usize, usize)> {
+        let row = arg(args, 0, "row", None)?;
+        let col = arg(args, 1, "column", None)?;
+        if row >= self.0.height() || col >= self.0.width() {
+            return Err(anyhow!(
+                "the grid has rows 0 to {} and columns 0 to {}",
+                self.0.height() - 1,
+                self.0.width() - 1
+            ));
+        }
+        Ok((col, row))
+    }
+}
+
+impl Session for Forest {
+    fn run(&mut self, command: &str, args: &[&str]) -> Result<String> {
+        let grid = &self.0;
+        match command {
+            "score" => {
+                let (x, y) = self.tree(args)?;
+                let distances = Dir4::ALL
+                    .iter()
+                    .map(|&dir| {
+                        format!("{dir} {}", viewing_distance(grid, x, y, dir, grid[(x, y)]))
+                    })
+                    .collect::<Vec<_>>();
+                Ok(format!(
+                    "{} ({})",
+                    scenic_score(grid, x, y),
+                    distances.join(", ")
+                ))
+            }
+            "visible" => {
+                let (x, y) = self.tree(args)?;
+                Ok(if is_visible(grid, x, y) { "yes" } else { "no" }.to_string())
+            }
+            "best" => {
+                let ((x, y), score) = grid
+                    .iter()
+                    .map(|((x, y), _)| ((x, y), scenic_score(grid, x, y)))
+                    .max_by_key(|&((x, y), score)| (score, Reverse((y, x))))
+                    .ok_or_else(|| anyhow!("the grid is empty"))?;
+                Ok(format!("row {y}, column {x}: {score}"))
+            }
+            _ => Err(unknown(command)),
+        }
+    }
+}
+
+/// Whether every tree between `(x, y)` and some edge is shorter than it.
+fn is_visible(grid: &Grid<u8>, x: usize, y: usize) -> bool {
+    let height = grid[(x, y)];
+    Dir4::ALL
+        .iter()
+        .any(|&dir| grid.ray(x, y, dir).all(|(_, &tree)| tree < height))
+}
+
+fn scenic_score(grid: &Grid<u8>, x: usize, y: usize) -> usize {
+    Dir4::ALL
+        .iter()
+        .map(|&dir| viewing_distance(grid, x, y, dir, grid[(x, y)]))
+        .product()
+}
+
+/// How many trees can be seen from `(x, y)` looking `dir`, up to and
+/// including the first one at least as tall as `height`.
+fn viewing_distance(grid: &Grid<u8>, x: usize, y: usize, dir: Dir4, height: u8) -> usize {
+    let mut seen = 0;
+    for (_, &tree) in grid.ray(x, y, dir) {
+        seen += 1;
+        if tree >= height {
+            break;
+        }
+    }
+    seen
+}
+
+impl Generator for Eight {
+    const UNIT: &'static str = "rows of a square grid";
+
+    fn generate(rng: &mut Rng, size: usize, out: &mut dyn Write) -> io::Result<()> {
+        let mut row = vec![b'\n'; size + 1];
+        for _ in 0..size.max(1) {
+            for tree in &mut row[..size] {
+                *tree = b'0' + rng.below(10) as u8;
+            }
+            out.write_all(&row)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "30373
+25512
+65332
+33549
+35390
+";
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(Eight::part1(&Eight::parse(EXAMPLE).unwrap()), 21);
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(Eight::part2(&Eight::parse(EXAMPLE).unwrap()), 8);
+    }
+
+    #[test]
+    fn visible_view_marks_visible_trees() {
+        let grid = Eight::parse(EXAMPLE).unwrap();
+        let mut frames = Vec::new();
+        Eight::render(&grid, "visible", &mut |frame| {
+            frames.push(frame.clone());
+            Ok(())
+        })
+        .unwrap();
+        assert_eq!(frames.len(), 1);
+        let green = frames[0]
+            .iter()
+            .filter(|(_, colour)| colour.1 > colour.0)
+            .count();
+        assert_eq!(green, 21);
+    }
+
+    #[test]
+    fn repl_scores_trees() {
+        let mut session = Eight::session(Eight::parse(EXAMPLE).unwrap());
+        assert!(session
+            .run("score", &["3", "2"])
+            .unwrap()
+            .starts_with("8 ("));
+        assert_eq!(session.run("visible", &["1", "1"]).unwrap(), "yes");
+        assert_eq!(session.run("visible", &["2", "2"]).unwrap(), "no");
+        assert_eq!(session.run("best", &[]).unwrap(), "row 3, column 2: 8");
+        assert!(session.run("score", &["5", "0"]).is_err());
+    }
+
+    #[test]
+    fn generated_edges_are_visible() {
+        for seed in 1..=5 {
+            let grid = Eight::parse(&DAY.generate(seed, 50)).unwrap();
+            let visible = Eight::part1(&grid);
+            assert!((4 * 49..=50 * 50).contains(&visible), "seed {seed}");
+            // Every inner tree sees at least one tree each way
+            assert!(Eight::part2(&grid) >= 1, "seed {seed}");
+        }
+    }
+}
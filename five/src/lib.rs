@@ -0,0 +1,523 @@
+use anyhow::anyhow;
+use anyhow::Result;
+use aoc_common::arg;
+use aoc_common::lines;
+use aoc_common::unknown;
+use aoc_common::Animate;
+use aoc_common::Animation;
+use aoc_common::Day;
+use aoc_common::Generator;
+use aoc_common::Line;
+use aoc_common::ParseError;
+use aoc_common::Part;
+use aoc_common::Repl;
+use aoc_common::Rng;
+use aoc_common::Session;
+use aoc_common::Solution;
+use std::fmt;
+use std::io;
+use std::io::Write;
+use tracing::debug;
+use tracing::trace;
+
+pub const DAY: Day = Day::new::<Five>(5, "five")
+    .with_animate::<Five>()
+    .with_repl::<Five>();
+
+pub struct Five;
+
+impl Solution for Five {
+    type Input = (Stacks, Vec<Op>);
+    type Output1 = String;
+    type Output2 = String;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        let (s, o) = read_input(input);
+        let stacks = parse_stacks(s)?;
+        let ops = parse_ops(o, stacks.0.len())?;
+        Ok((stacks, ops))
+    }
+
+    fn part1((stacks, ops): &Self::Input) -> String {
+        let mut stacks = stacks.clone();
+        for op in ops {
+            stacks.apply(op, Crane::Mover9000);
+        }
+        debug!(heights = ?stacks.heights(), "final stacks");
+        stacks.tops()
+    }
+
+    fn part2((stacks, ops): &Self::Input) -> String {
+        let mut stacks = stacks.clone();
+        for op in ops {
+            stacks.apply(op, Crane::Mover9001);
+        }
+        stacks.tops()
+    }
+}
+
+impl Animate for Five {
+    /// Part 1's crane moves crates one at a time, part 2's several at once.
+    fn animation((stacks, ops): &Self::Input, part: Part) -> Box<dyn Animation + '_> {
+        let crane = if part == Part::One {
+            Crane::Mover9000
+        } else {
+            Crane::Mover9001
+        };
+        Box::new(CraneAnimation {
+            start: stacks,
+            ops,
+            crane,
+            stacks: stacks.clone(),
+            done: 0,
+        })
+    }
+}
+
+/// Carries out one move per frame.
+struct CraneAnimation<'a> {
+    start: &'a Stacks,
+    ops: &'a [Op],
+    crane: Crane,
+    stacks: Stacks,
+    done: usize,
+}
+
+impl Animation for CraneAnimation<'_> {
+    fn frames(&self) -> usize {
+        self.ops.len() + 1
+    }
+
+    fn reset(&mut self) {
+        self.stacks = self.start.clone();
+        self.done = 0;
+    }
+
+    fn step(&mut self) {
+        if let Some(op) = self.ops.get(self.done) {
+            self.stacks.apply(op, self.crane);
+            self.done += 1;
+        }
+    }
+
+    fn draw(&self, _width: usize, height: usize) -> String {
+        let header = match self.done.checked_sub(1).map(|last| &self.ops[last]) {
+            Some(op) => format!("{op} ({}/{})", self.done, self.ops.len()),
+            None => format!("{} moves to go", self.ops.len()),
+        };
+        format!(
+            "{header}, tops {}\n{}",
+            self.stacks.tops(),
+            self.stacks.drawing(height.saturating_sub(2))
+        )
+    }
+}
+
+impl Repl for Five {
+    const COMMANDS: &'static [(&'static str, &'static str)] = &[
+        ("stack N", "the crates in stack N, bottom first"),
+        ("step [N]", "carry out the next N moves, 1 by default"),
+        ("show", "draw the stacks"),
+        ("crane 9000|9001", "switch crane model and start over"),
+        ("reset", "put the crates back where they started"),
+    ];
+
+    fn session((stacks, ops): Self::Input) -> Box<dyn Session> {
+        Box::new(Yard {
+            stacks: stacks.clone(),
+            start: stacks,
+            ops,
+            crane: Crane::Mover9000,
+            done: 0,
+        })
+    }
+}
+
+/// The stacks part way through the moves, for the repl.
+struct Yard {
+    start: Stacks,
+    ops: Vec<Op>,
+    crane: Crane,
+    stacks: Stacks,
+    done: usize,
+}
+
+impl Session for Yard {
+    fn run(&mut self, command: &str, args: &[&str]) -> Result<String> {
+        match command {
+            "stack" => {
+                let index = arg::<usize>(args, 0, "stack", None)?;
+                match index.checked_sub(1).and_then(|i| self.stacks.0.get(i)) {
+                    Some(stack) if stack.is_empty() => Ok("(empty)".to_string()),
+                    Some(stack) => Ok(stack.iter().collect()),
+                    None => Err(anyhow!("there are stacks 1 to {}", self.stacks.0.len())),
+                }
+            }
+            "step" => {
+                let mut out = String::new();
+                for _ in 0..arg(args, 0, "count", Some(1))? {
+                    let Some(op) = self.ops.get(self.done) else {
+                        out += "no moves left\n";
+                        break;
+                    };
+                    self.stacks.apply(op, self.crane);
+                    self.done += 1;
+                    out += &format!("{op}\n");
+                }
+                out += &format!(
+                    "{}/{} moves done, tops {}",
+                    self.done,
+                    self.ops.len(),
+                    self.stacks.tops()
+                );
+                Ok(out)
+            }
+            "show" => Ok(self.stacks.to_string()),
+            "crane" => {
+                self.crane = match arg::<u32>(args, 0, "model", None)? {
+                    9000 => Crane::Mover9000,
+                    9001 => Crane::Mover9001,
+                    other => return Err(anyhow!("there's no CrateMover {other}")),
+                };
+                self.run("reset", &[])
+            }
+            "reset" => {
+                self.stacks = self.start.clone();
+                self.done = 0;
+                Ok(format!("{:?}, {} moves to go", self.crane, self.ops.len()))
+            }
+            _ => Err(unknown(command)),
+        }
+    }
+}
+
+const STACKS: usize = 9;
+
+impl Generator for Five {
+    const UNIT: &'static str = "moves";
+
+    fn generate(rng: &mut Rng, size: usize, out: &mut dyn Write) -> io::Result<()> {
+        let max_height = (size / 100).clamp(8, 50) as i64;
+        let mut heights = (0..STACKS)
+            .map(|_| rng.range(1..=max_height) as usize)
+            .collect::<Vec<_>>();
+        // Drawn the same way the stacks show themselves, so the parser reads
+        // back exactly what was generated
+        let mut stacks = Stacks::new(STACKS);
+        for (index, &height) in heights.iter().enumerate() {
+            for _ in 0..height {
+                stacks.push(index + 1, (b'A' + rng.below(26) as u8) as char);
+            }
+        }
+        writeln!(out, "{stacks}")?;
+        writeln!(out)?;
+        // Only ever moves crates that are there, which is the same for both
+        // crane models
+        for _ in 0..size {
+            let from = loop {
+                let from = rng.below(STACKS);
+                if heights[from] > 0 {
+                    break from;
+                }
+            };
+            let to = (from + 1 + rng.below(STACKS - 1)) % STACKS;
+            let num = rng.range(1..=heights[from] as i64) as usize;
+            heights[from] -= num;
+            heights[to] += num;
+            writeln!(out, "move {num} from {} to {}", from + 1, to + 1)?;
+        }
+        Ok(())
+    }
+}
+
+/// Which crane model does the moving.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Crane {
+    /// Moves one crate at a time, reversing their order.
+    Mover9000,
+    /// Moves all the crates at once, keeping their order.
+    Mover9001,
+}
+
+#[derive(Debug, Clone)]
+pub struct Stacks(Vec<Vec<char>>);
+
+impl Stacks {
+    fn new(len: usize) -> Self {
+        let mut inner: Vec<Vec<char>> = Vec::new();
+        for _ in 0..len {
+            inner.push(Vec::new());
+        }
+        Self(inner)
+    }
+
+    fn push(&mut self, index: usize, c: char) {
+        self.0[index - 1].push(c)
+    }
+
+    fn push_mult(&mut self, index: usize, cs: Vec<char>) {
+        self.0[index - 1].extend(cs)
+    }
+
+    fn pop(&mut self, index: usize) -> Option<char> {
+        self.0[index - 1].pop()
+    }
+
+    fn pop_mult(&mut self, index: usize, num: usize) -> Vec<char> {
+        let mut res = Vec::new();
+        for _ in 0..num {
+            if let Some(c) = self.0[index - 1].pop() {
+                res.push(c)
+            }
+        }
+        res.into_iter().rev().collect()
+    }
+
+    fn heights(&self) -> Vec<usize> {
+        self.0.iter().map(Vec::len).collect()
+    }
+
+    fn tops(&self) -> String {
+        self.0.iter().filter_map(|x| x.last()).collect()
+    }
+
+    fn apply(&mut self, op: &Op, crane: Crane) {
+        trace!(
+            num = op.num,
+            from = op.from,
+            to = op.to,
+            ?crane,
+            "moving crates"
+        );
+        match crane {
+            Crane::Mover9000 => {
+                for _ in 0..op.num {
+                    if let Some(c) = self.pop(op.from) {
+                        self.push(op.to, c);
+                    }
+                }
+            }
+            Crane::Mover9001 => {
+                let cs = self.pop_mult(op.from, op.num);
+                self.push_mult(op.to, cs);
+            }
+        }
+    }
+
+    /// Draws the stacks the way the puzzle input does, showing at most the
+    /// top `rows` rows of crates.
+    fn drawing(&self, rows: usize) -> String {
+        let tallest = self.0.iter().map(Vec::len).max().unwrap_or(0);
+        let mut out = String::new();
+        for row in (tallest.saturating_sub(rows)..tallest).rev() {
+            let line = self
+                .0
+                .iter()
+                .map(|stack| match stack.get(row) {
+                    Some(c) => format!("[{c}]"),
+                    None => "   ".to_string(),
+                })
+                .collect::<Vec<_>>()
+                .join(" ");
+            out.push_str(line.trim_end());
+            out.push('\n');
+        }
+        let numbers = (1..=self.0.len())
+            .map(|i| format!(" {i} "))
+            .collect::<Vec<_>>()
+            .join(" ");
+        out.push_str(numbers.trim_end());
+        out
+    }
+}
+
+impl fmt::Display for Stacks {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.drawing(usize::MAX))
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Op {
+    num: usize,
+    from: usize,
+    to: usize,
+}
+
+impl fmt::Display for Op {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "move {} from {} to {}", self.num, self.from, self.to)
+    }
+}
+
+fn parse_ops(s: Vec<Line>, stack_num: usize) -> Result<Vec<Op>, ParseError> {
+    let mut res = Vec::new();
+    for line in s {
+        if line.text.trim().is_empty() {
+            continue;
+        }
+        if let ["move", num, "from", from, "to", to] = line.words()[..] {
+            let num = line.parse::<usize>(num)?;
+            let from = parse_index(&line, from, stack_num)?;
+            let to = parse_index(&line, to, stack_num)?;
+            res.push(Op { num, from, to })
+        } else {
+            return Err(line.error("expected `move N from A to B`"));
+        }
+    }
+    Ok(res)
+}
+
+fn parse_index(line: &Line, token: &str, stack_num: usize) -> Result<usize, ParseError> {
+    let index = line.parse::<usize>(token)?;
+    if !(1..=stack_num).contains(&index) {
+        return Err(line.error_at(token, format!("no stack {index}, expected 1-{stack_num}")));
+    }
+    Ok(index)
+}
+
+fn parse_stacks(s: Vec<Line>) -> Result<Stacks, ParseError> {
+    let stack_num: usize = s
+        .iter()
+        .map(|x| x.text.chars().filter(|y| y.is_numeric()).count())
+        .max()
+        .ok_or_else(|| ParseError::new(1, 1, 0, "", "missing crate drawing"))?;
+    let mut stacks = Stacks::new(stack_num);
+    for line in s.into_iter().rev().skip(1) {
+        for (i, (pos, c)) in line.text.char_indices().enumerate() {
+            if c.is_alphabetic() {
+                let index = i / 4 + 1;
+                if index > stack_num {
+                    let token = &line.text[pos..pos + c.len_utf8()];
+                    return Err(line.error_at(token, "crate outside the numbered stacks"));
+                }
+                stacks.push(index, c);
+            }
+        }
+    }
+    Ok(stacks)
+}
+
+fn read_input(input: &str) -> (Vec<Line<'_>>, Vec<Line<'_>>) {
+    let stacks = lines(input)
+        .take_while(|line| !line.text.trim().is_empty())
+        .collect::<Vec<_>>();
+    let ops = lines(input).skip(stacks.len() + 1).collect::<Vec<_>>();
+    (stacks, ops)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = concat!(
+        "    [D]\n",
+        "[N] [C]\n",
+        "[Z] [M] [P]\n",
+        " 1   2   3\n",
+        "\n",
+        "move 1 from 2 to 1\n",
+        "move 3 from 1 to 3\n",
+        "move 2 from 2 to 1\n",
+        "move 1 from 1 to 2\n",
+    );
+
+    #[test]
+    fn parses_drawing() {
+        let (stacks, ops) = Five::parse(EXAMPLE).unwrap();
+        assert_eq!(
+            stacks.0,
+            vec![vec!['Z', 'N'], vec!['M', 'C', 'D'], vec!['P']]
+        );
+        assert_eq!(ops.len(), 4);
+    }
+
+    #[test]
+    fn drawing_matches_the_input() {
+        let (stacks, _) = Five::parse(EXAMPLE).unwrap();
+        assert_eq!(stacks.to_string(), EXAMPLE.split("\n\n").next().unwrap());
+        assert_eq!(stacks.drawing(1), "    [D]\n 1   2   3");
+    }
+
+    #[test]
+    fn animation_ends_on_each_parts_answer() {
+        let input = Five::parse(EXAMPLE).unwrap();
+        for (part, tops) in [(Part::One, "CMZ"), (Part::Two, "MCD")] {
+            let mut animation = Five::animation(&input, part);
+            assert_eq!(animation.frames(), 5);
+            for _ in 0..animation.frames() {
+                animation.step();
+            }
+            assert!(animation.draw(80, 24).contains(&format!("tops {tops}")));
+            animation.reset();
+            assert!(animation
+                .draw(80, 24)
+                .starts_with("4 moves to go, tops NDP"));
+        }
+    }
+
+    #[test]
+    fn repl_moves_crates_a_step_at_a_time() {
+        let mut session = Five::session(Five::parse(EXAMPLE).unwrap());
+        assert_eq!(session.run("stack", &["2"]).unwrap(), "MCD");
+        assert_eq!(
+            session.run("step", &["2"]).unwrap(),
+            "move 1 from 2 to 1\nmove 3 from 1 to 3\n2/4 moves done, tops CZ"
+        );
+        assert_eq!(session.run("stack", &["1"]).unwrap(), "(empty)");
+        session.run("crane", &["9001"]).unwrap();
+        assert!(session
+            .run("step", &["9"])
+            .unwrap()
+            .ends_with("no moves left\n4/4 moves done, tops MCD"));
+        assert!(session.run("stack", &["4"]).is_err());
+    }
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(Five::part1(&Five::parse(EXAMPLE).unwrap()), "CMZ");
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(Five::part2(&Five::parse(EXAMPLE).unwrap()), "MCD");
+    }
+
+    #[test]
+    fn emptied_stack_has_no_top() {
+        let input = "[A]\n 1   2\n\nmove 1 from 1 to 2\n";
+        assert_eq!(Five::part1(&Five::parse(input).unwrap()), "A");
+    }
+
+    #[test]
+    fn generated_drawing_parses_back() {
+        let input = DAY.generate(1, 200);
+        let (drawing, _) = input.split_once("\n\n").unwrap();
+        let (stacks, _) = Five::parse(&input).unwrap();
+        assert_eq!(stacks.0.len(), STACKS);
+        assert_eq!(stacks.to_string(), drawing);
+    }
+
+    #[test]
+    fn generated_moves_keep_every_crate() {
+        let crates = |stacks: &Stacks| {
+            let mut crates = stacks.0.concat();
+            crates.sort();
+            crates
+        };
+        for seed in 1..=5 {
+            let (start, ops) = Five::parse(&DAY.generate(seed, 200)).unwrap();
+            let mut one = start.clone();
+            let mut two = start.clone();
+            for op in &ops {
+                one.apply(op, Crane::Mover9000);
+                two.apply(op, Crane::Mover9001);
+            }
+            // Neither crane reaches for a crate that isn't there, so both
+            // end up with the same heights and every crate still somewhere
+            assert_eq!(one.heights(), two.heights(), "seed {seed}");
+            assert_eq!(crates(&one), crates(&start), "seed {seed}");
+            assert_eq!(crates(&two), crates(&start), "seed {seed}");
+        }
+    }
+}
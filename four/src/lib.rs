@@ -0,0 +1,157 @@
+use anyhow::Result;
+use aoc_common::lines;
+use aoc_common::Day;
+use aoc_common::Generator;
+use aoc_common::Line;
+use aoc_common::LineReader;
+use aoc_common::ParseError;
+use aoc_common::Rng;
+use aoc_common::Solution;
+use aoc_common::Stream;
+use std::io;
+use std::io::Write;
+use std::ops::RangeInclusive;
+use tracing::debug;
+
+pub const DAY: Day = Day::new::<Four>(4, "four").with_stream::<Four>();
+
+pub struct Four;
+
+type Pair = (RangeInclusive<u64>, RangeInclusive<u64>);
+
+impl Solution for Four {
+    type Input = Vec<Pair>;
+    type Output1 = usize;
+    type Output2 = usize;
+    // 2: rejects reversed ranges
+    const VERSION: &'static str = "2";
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        let pairs = read_input(input)?;
+        debug!(pairs = pairs.len(), "read section pairs");
+        Ok(pairs)
+    }
+
+    fn part1(input: &Self::Input) -> usize {
+        input.iter().filter(|pair| full_overlap(pair)).count()
+    }
+
+    fn part2(input: &Self::Input) -> usize {
+        input.iter().filter(|pair| partial_overlap(pair)).count()
+    }
+}
+
+impl Stream for Four {
+    fn stream(lines: &mut LineReader<'_>) -> Result<(usize, usize)> {
+        let (mut full, mut partial) = (0, 0);
+        let mut pairs = 0;
+        while let Some(line) = lines.next_line()? {
+            let pair = read_pair(&line)?;
+            full += full_overlap(&pair) as usize;
+            partial += partial_overlap(&pair) as usize;
+            pairs += 1;
+        }
+        debug!(pairs, "streamed section pairs");
+        Ok((full, partial))
+    }
+}
+
+fn full_overlap((e1, e2): &Pair) -> bool {
+    (e1.start() <= e2.start() && e1.end() >= e2.end())
+        || (e2.start() <= e1.start() && e2.end() >= e1.end())
+}
+
+fn partial_overlap((e1, e2): &Pair) -> bool {
+    (e1.contains(e2.start()) || e1.contains(e2.end()))
+        || (e2.contains(e1.start()) || e2.contains(e1.end()))
+}
+
+impl Generator for Four {
+    const UNIT: &'static str = "pairs";
+
+    fn generate(rng: &mut Rng, size: usize, out: &mut dyn Write) -> io::Result<()> {
+        for _ in 0..size {
+            let first = rng.range(1..=99);
+            let first_end = rng.range(first..=99);
+            let second = rng.range(1..=99);
+            let second_end = rng.range(second..=99);
+            writeln!(out, "{first}-{first_end},{second}-{second_end}")?;
+        }
+        Ok(())
+    }
+}
+
+fn read_input(input: &str) -> Result<Vec<Pair>, ParseError> {
+    lines(input).map(|line| read_pair(&line)).collect()
+}
+
+fn read_pair(line: &Line) -> Result<Pair, ParseError> {
+    if let [first, second] = line.text.split(',').collect::<Vec<_>>()[..] {
+        Ok((read_range(line, first)?, read_range(line, second)?))
+    } else {
+        Err(line.error("expected two comma separated ranges"))
+    }
+}
+
+fn read_range(line: &Line, range: &str) -> Result<RangeInclusive<u64>, ParseError> {
+    if let [start, end] = range.split('-').collect::<Vec<_>>()[..] {
+        let (start, end) = (line.parse::<u64>(start)?, line.parse::<u64>(end)?);
+        if start > end {
+            return Err(line.error_at(range, "range ends before it starts"));
+        }
+        Ok(start..=end)
+    } else {
+        Err(line.error_at(range, "expected a range like 2-4"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "2-4,6-8
+2-3,4-5
+5-7,7-9
+2-8,3-7
+6-6,4-6
+2-6,4-8
+";
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(Four::part1(&Four::parse(EXAMPLE).unwrap()), 2);
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(Four::part2(&Four::parse(EXAMPLE).unwrap()), 4);
+    }
+
+    #[test]
+    fn rejects_reversed_ranges() {
+        let err = Four::parse("2-4,6-8\n5-3,1-2\n").unwrap_err();
+        assert_eq!((err.line, err.column), (2, 1));
+        let err = Four::parse("1-2,9-8\n").unwrap_err();
+        assert_eq!((err.line, err.column), (1, 5));
+        assert!(Four::parse("3-3,1-2\n").is_ok());
+    }
+
+    #[test]
+    fn streaming_matches_parsing() {
+        for input in [EXAMPLE.to_string(), DAY.generate(1, 200)] {
+            let parsed = Four::parse(&input).unwrap();
+            let streamed = Four::stream(&mut LineReader::new(&mut input.as_bytes())).unwrap();
+            assert_eq!(streamed, (Four::part1(&parsed), Four::part2(&parsed)));
+        }
+    }
+
+    #[test]
+    fn generated_containment_is_overlap() {
+        for seed in 1..=5 {
+            let pairs = Four::parse(&DAY.generate(seed, 200)).unwrap();
+            let (contained, overlapping) = (Four::part1(&pairs), Four::part2(&pairs));
+            assert!(contained <= overlapping, "seed {seed}");
+            assert!(overlapping <= pairs.len(), "seed {seed}");
+        }
+    }
+}
@@ -0,0 +1,754 @@
+use anyhow::anyhow;
+use anyhow::Error;
+use anyhow::Result;
+use aoc_common::arg;
+use aoc_common::lines;
+use aoc_common::unknown;
+use aoc_common::Animate;
+use aoc_common::Animation;
+use aoc_common::Day;
+use aoc_common::Dir4;
+use aoc_common::Frame;
+use aoc_common::Frames;
+use aoc_common::Generator;
+use aoc_common::Grid;
+use aoc_common::LineReader;
+use aoc_common::ParseError;
+use aoc_common::Part;
+use aoc_common::Point;
+use aoc_common::Render;
+use aoc_common::Repl;
+use aoc_common::Rgb;
+use aoc_common::Rng;
+use aoc_common::Session;
+use aoc_common::Solution;
+use aoc_common::Stream;
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::fmt;
+use std::io;
+use std::io::Write;
+use std::str::FromStr;
+use tracing::debug;
+use tracing::trace;
+
+pub const DAY: Day = Day::new::<Nine>(9, "nine")
+    .with_render::<Nine>()
+    .with_animate::<Nine>()
+    .with_stream::<Nine>()
+    .with_repl::<Nine>();
+
+pub struct Nine;
+
+impl Solution for Nine {
+    type Input = Vec<Motion>;
+    type Output1 = usize;
+    type Output2 = usize;
+    // 2: rejects motions over a million steps
+    const VERSION: &'static str = "2";
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        read_input(input)
+    }
+
+    fn part1(input: &Self::Input) -> usize {
+        trail_len(input, 2)
+    }
+
+    fn part2(input: &Self::Input) -> usize {
+        trail_len(input, 10)
+    }
+}
+
+impl Stream for Nine {
+    /// Both ropes move as each motion is read, keeping only their knots and
+    /// the cells each tail has visited. Memory grows with how many different
+    /// cells that is, not with the length of the input; a head that wanders
+    /// off drags the tails to new cells and still costs memory.
+    fn stream(lines: &mut LineReader<'_>) -> Result<(usize, usize)> {
+        let mut short = Tail::new(2);
+        let mut long = Tail::new(10);
+        while let Some(line) = lines.next_line()? {
+            let motion = line.parse::<Motion>(line.text)?;
+            short.move_head(motion);
+            long.move_head(motion);
+        }
+        Ok((short.visited.len(), long.visited.len()))
+    }
+}
+
+/// A rope that only remembers where its tail has been, for solving.
+#[derive(Debug)]
+struct Tail {
+    body: Vec<Point>,
+    visited: HashSet<Point>,
+}
+
+impl Tail {
+    fn new(body_len: usize) -> Self {
+        Self {
+            body: vec![Point::ORIGIN; body_len],
+            visited: HashSet::from([Point::ORIGIN]),
+        }
+    }
+
+    fn move_head(&mut self, motion: Motion) {
+        let step = motion.dir.step();
+        for _ in 0..motion.steps {
+            self.body[0] += step;
+            for knot in 1..self.body.len() {
+                pull(self.body[knot - 1], &mut self.body[knot]);
+            }
+            self.visited.insert(self.body[self.body.len() - 1]);
+        }
+    }
+}
+
+/// Pulls `knot` one step towards `lead` once they stop touching.
+fn pull(lead: Point, knot: &mut Point) -> bool {
+    let moved = lead.chebyshev(*knot) > 1;
+    if moved {
+        *knot += (lead - *knot).signum();
+    }
+    moved
+}
+
+impl Generator for Nine {
+    const UNIT: &'static str = "motions";
+
+    fn generate(rng: &mut Rng, size: usize, out: &mut dyn Write) -> io::Result<()> {
+        for _ in 0..size {
+            writeln!(
+                out,
+                "{} {}",
+                rng.pick(&['U', 'D', 'L', 'R']),
+                rng.range(1..=20)
+            )?;
+        }
+        Ok(())
+    }
+}
+
+impl Render for Nine {
+    const VIEWS: &'static [&'static str] = &["rope", "short-rope"];
+
+    /// Draws the ten knot rope of part 2, or the two knot one of part 1 for
+    /// `short-rope`, after every motion: the head's trail in blue, the tail's
+    /// in orange and the knots from red at the head to yellow at the tail.
+    fn render(input: &Self::Input, view: &str, frames: Frames<'_>) -> io::Result<()> {
+        let knots = if view == "short-rope" { 2 } else { 10 };
+        // The rope never leaves the box its head travels in, so the finished
+        // map says how big every frame needs to be
+        let (min, max) = simulate(input, knots).bounds();
+        let (width, height) = ((max.x - min.x + 1) as usize, (max.y - min.y + 1) as usize);
+        let mut map = GrowingMap::new(knots);
+        frames(&map.frame(min, width, height))?;
+        for motion in input {
+            map.move_head(*motion);
+            frames(&map.frame(min, width, height))?;
+        }
+        Ok(())
+    }
+}
+
+fn simulate(input: &[Motion], body_len: usize) -> GrowingMap {
+    let mut map = GrowingMap::new(body_len);
+    for motion in input {
+        map.move_head(*motion);
+    }
+    map
+}
+
+fn trail_len(input: &[Motion], body_len: usize) -> usize {
+    let mut rope = Tail::new(body_len);
+    for motion in input {
+        rope.move_head(*motion);
+    }
+    rope.visited.len()
+}
+
+/// A rope and the trails left by its head and tail, for drawing. Only the
+/// cells something has passed through are kept, so it costs memory for the
+/// length of the trails rather than the area they span.
+#[derive(Debug)]
+struct GrowingMap {
+    trails: HashMap<Point, Cell>,
+    body: Vec<Point>,
+}
+
+impl GrowingMap {
+    fn new(body_len: usize) -> Self {
+        Self {
+            trails: HashMap::new(),
+            body: vec![Point::ORIGIN; body_len],
+        }
+    }
+
+    fn check_body(&self, pos: Point) -> Option<usize> {
+        self.body.iter().position(|&body| body == pos)
+    }
+
+    fn calc_trail(&self) -> usize {
+        self.trails
+            .values()
+            .filter(|cell| matches!(cell, Cell::Trail(Rope::Tail | Rope::Both)))
+            .count()
+    }
+
+    /// The top left and bottom right corners of everywhere the rope has been.
+    fn bounds(&self) -> (Point, Point) {
+        let mut min = self.body[0];
+        let mut max = self.body[0];
+        for &pos in self.trails.keys().chain(&self.body) {
+            min = Point::new(min.x.min(pos.x), min.y.min(pos.y));
+            max = Point::new(max.x.max(pos.x), max.y.max(pos.y));
+        }
+        (min, max)
+    }
+
+    /// Draws the map on a `width` by `height` frame whose top left cell is
+    /// `corner`. Anything outside the frame is left out.
+    fn frame(&self, corner: Point, width: usize, height: usize) -> Frame {
+        let mut frame = Grid::new(width, height, Rgb(16, 16, 24));
+        for (&pos, cell) in &self.trails {
+            let colour = match cell {
+                Cell::None => continue,
+                Cell::Trail(Rope::Head) => Rgb(50, 80, 150),
+                Cell::Trail(_) => Rgb(240, 140, 30),
+            };
+            if let Some(pixel) = frame.at_mut(pos - corner) {
+                *pixel = colour;
+            }
+        }
+        // Back to front so the head is drawn over any knots sharing its cell
+        let last = (self.body.len() - 1).max(1) as f64;
+        for (knot, &pos) in self.body.iter().enumerate().rev() {
+            if let Some(pixel) = frame.at_mut(pos - corner) {
+                *pixel = Rgb(255, 50, 50).mix(Rgb(255, 235, 80), knot as f64 / last);
+            }
+        }
+        frame
+    }
+
+    fn leave_cell(&mut self, pos: Point, rope: Rope) {
+        let cell = self.trails.entry(pos).or_insert(Cell::None);
+        *cell = match (rope, *cell) {
+            (Rope::Head, Cell::None) => Cell::Trail(Rope::Head),
+            (Rope::Head, Cell::Trail(Rope::Tail)) => Cell::Trail(Rope::Both),
+            (Rope::Tail, Cell::None) => Cell::Trail(Rope::Tail),
+            (Rope::Tail, Cell::Trail(Rope::Head)) => Cell::Trail(Rope::Both),
+            (Rope::Both, _) => panic!("Both cannot leave cell simultaneously"),
+            (_, cell) => cell,
+        };
+    }
+
+    fn move_head(&mut self, motion: Motion) {
+        debug!(dir = %motion.dir, steps = motion.steps, "moving head");
+        let step = motion.dir.step();
+        for _ in 0..motion.steps {
+            self.leave_cell(self.body[0], Rope::Head);
+            self.body[0] += step;
+            for knot in 1..self.body.len() {
+                self.move_knot(knot);
+            }
+            let tail = self.body[self.body.len() - 1];
+            self.leave_cell(tail, Rope::Tail);
+        }
+    }
+
+    fn move_knot(&mut self, knot: usize) {
+        let lead = self.body[knot - 1];
+        let cur = &mut self.body[knot];
+        if pull(lead, cur) {
+            trace!(knot, x = cur.x, y = cur.y, "knot moved");
+        }
+    }
+}
+
+impl GrowingMap {
+    /// What's at `pos`: a knot, or else the trail left there.
+    fn glyph(&self, pos: Point) -> String {
+        match self.check_body(pos) {
+            Some(0) => Rope::Head.to_string(),
+            Some(body) if body < self.body.len() - 1 => body.to_string(),
+            Some(_) => Rope::Tail.to_string(),
+            None => self.trails.get(&pos).unwrap_or(&Cell::None).to_string(),
+        }
+    }
+
+    /// A `width` by `height` window of the map centred on `centre`.
+    fn window(&self, centre: Point, width: usize, height: usize) -> String {
+        let corner = centre - Point::new(width as i64 / 2, height as i64 / 2);
+        let mut out = String::new();
+        for y in 0..height as i64 {
+            for x in 0..width as i64 {
+                out.push_str(&self.glyph(corner + Point::new(x, y)));
+            }
+            out.push('\n');
+        }
+        out
+    }
+}
+
+impl fmt::Display for GrowingMap {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let (min, max) = self.bounds();
+        for y in min.y..=max.y {
+            for x in min.x..=max.x {
+                write!(f, "[{}]", self.glyph(Point::new(x, y)))?;
+            }
+            writeln!(f)?;
+        }
+        write!(f, "")
+    }
+}
+
+impl Animate for Nine {
+    /// Part 1's two knot rope, or part 2's ten knots otherwise.
+    fn animation(input: &Self::Input, part: Part) -> Box<dyn Animation + '_> {
+        let knots = if part == Part::One { 2 } else { 10 };
+        Box::new(RopeAnimation {
+            motions: input,
+            walk: Walk::new(knots),
+        })
+    }
+}
+
+/// A rope being moved one step of the head at a time.
+#[derive(Debug)]
+struct Walk {
+    map: GrowingMap,
+    /// The motion being made and how many of its steps are done.
+    motion: usize,
+    taken: usize,
+}
+
+impl Walk {
+    fn new(knots: usize) -> Self {
+        Self {
+            map: GrowingMap::new(knots),
+            motion: 0,
+            taken: 0,
+        }
+    }
+
+    /// Takes the next step of `motions`, returning false once they're done.
+    fn step(&mut self, motions: &[Motion]) -> bool {
+        while let Some(motion) = motions.get(self.motion) {
+            if self.taken < motion.steps {
+                self.map.move_head(Motion {
+                    dir: motion.dir,
+                    steps: 1,
+                });
+                self.taken += 1;
+                return true;
+            }
+            self.motion += 1;
+            self.taken = 0;
+        }
+        false
+    }
+
+    /// Which motion is under way and how far the tail has been.
+    fn status(&self, motions: &[Motion]) -> String {
+        let header = match motions.get(self.motion) {
+            Some(motion) => format!(
+                "motion {}/{}: {motion}, step {}",
+                self.motion + 1,
+                motions.len(),
+                self.taken
+            ),
+            None => "done".to_string(),
+        };
+        format!("{header}, tail visited {}", self.map.calc_trail())
+    }
+}
+
+/// Moves the rope one step per frame, keeping the head in the middle.
+struct RopeAnimation<'a> {
+    motions: &'a [Motion],
+    walk: Walk,
+}
+
+impl Animation for RopeAnimation<'_> {
+    fn frames(&self) -> usize {
+        1 + self
+            .motions
+            .iter()
+            .map(|motion| motion.steps)
+            .sum::<usize>()
+    }
+
+    fn reset(&mut self) {
+        self.walk = Walk::new(self.walk.map.body.len());
+    }
+
+    fn step(&mut self) {
+        self.walk.step(self.motions);
+    }
+
+    fn draw(&self, width: usize, height: usize) -> String {
+        let map = &self.walk.map;
+        format!(
+            "{}\n{}",
+            self.walk.status(self.motions),
+            map.window(map.body[0], width, height.saturating_sub(1))
+        )
+    }
+}
+
+impl Repl for Nine {
+    const COMMANDS: &'static [(&'static str, &'static str)] = &[
+        ("step [N]", "move the head N steps, 1 by default"),
+        (
+            "show [W H]",
+            "draw a W by H window around the head, 21 by 11 by default",
+        ),
+        ("knots N", "start over with a rope of N knots"),
+        ("reset", "start over with the same rope"),
+    ];
+
+    /// Starts with part 2's ten knot rope.
+    fn session(motions: Vec<Motion>) -> Box<dyn Session> {
+        Box::new(RopeSession {
+            motions,
+            walk: Walk::new(10),
+        })
+    }
+}
+
+struct RopeSession {
+    motions: Vec<Motion>,
+    walk: Walk,
+}
+
+impl Session for RopeSession {
+    fn run(&mut self, command: &str, args: &[&str]) -> Result<String> {
+        match command {
+            "step" => {
+                for _ in 0..arg::<usize>(args, 0, "count", Some(1))? {
+                    if !self.walk.step(&self.motions) {
+                        break;
+                    }
+                }
+                let body = &self.walk.map.body;
+                let (head, tail) = (body[0], body[body.len() - 1]);
+                Ok(format!(
+                    "{}\nhead at {}, {}, tail at {}, {}",
+                    self.walk.status(&self.motions),
+                    head.x,
+                    head.y,
+                    tail.x,
+                    tail.y
+                ))
+            }
+            "show" => {
+                let width = arg(args, 0, "width", Some(21))?;
+                let height = arg(args, 1, "height", Some(11))?;
+                let map = &self.walk.map;
+                Ok(map.window(map.body[0], width, height))
+            }
+            "knots" => {
+                let knots = arg::<usize>(args, 0, "knots", None)?;
+                if knots < 2 {
+                    return Err(anyhow!("a rope needs at least 2 knots"));
+                }
+                self.walk = Walk::new(knots);
+                Ok(self.walk.status(&self.motions))
+            }
+            "reset" => {
+                self.walk = Walk::new(self.walk.map.body.len());
+                Ok(self.walk.status(&self.motions))
+            }
+            _ => Err(unknown(command)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Rope {
+    Head,
+    Tail,
+    Both,
+}
+
+impl FromStr for Rope {
+    type Err = Error;
+    fn from_str(s: &str) -> Result<Self> {
+        let val = match s {
+            "H" => Rope::Head,
+            "T" => Rope::Tail,
+            "B" => Rope::Both,
+            _ => return Err(anyhow!("Invalid rope value {s}")),
+        };
+        Ok(val)
+    }
+}
+
+impl fmt::Display for Rope {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let val = match self {
+            Rope::Head => "H",
+            Rope::Tail => "T",
+            Rope::Both => "B",
+        };
+        write!(f, "{}", val)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Cell {
+    None,
+    Trail(Rope),
+}
+
+impl FromStr for Cell {
+    type Err = Error;
+    fn from_str(s: &str) -> Result<Self> {
+        let val = match s {
+            " " => Cell::None,
+            "." => Cell::Trail(Rope::Head),
+            "," => Cell::Trail(Rope::Tail),
+            ";" => Cell::Trail(Rope::Both),
+            _ => return Err(anyhow!("Invalid cell value {s}")),
+        };
+        Ok(val)
+    }
+}
+
+impl fmt::Display for Cell {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let val = match self {
+            Cell::None => " ",
+            Cell::Trail(rope) => match rope {
+                Rope::Head => ".",
+                Rope::Tail => ",",
+                Rope::Both => ";",
+            },
+        };
+        write!(f, "{}", val)
+    }
+}
+
+/// The longest motion accepted. Every step can take the tail to a new cell
+/// that has to be remembered, so this keeps one line of input from asking
+/// for more memory than a machine has.
+const MAX_STEPS: usize = 1_000_000;
+
+/// One line of the input: which way the head moves and how far.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Motion {
+    pub dir: Dir4,
+    pub steps: usize,
+}
+
+impl FromStr for Motion {
+    type Err = Error;
+    fn from_str(s: &str) -> Result<Self> {
+        if let [dir, steps] = &s.split(' ').collect::<Vec<_>>()[..] {
+            let steps = steps
+                .parse::<usize>()
+                .map_err(|_| anyhow!("invalid value {steps}"))?;
+            if steps > MAX_STEPS {
+                return Err(anyhow!("{steps} steps is too far, at most {MAX_STEPS}"));
+            }
+            return Ok(Motion {
+                dir: dir.parse()?,
+                steps,
+            });
+        }
+        Err(anyhow!("Invalid motion: {s}"))
+    }
+}
+
+impl fmt::Display for Motion {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} {}", self.dir, self.steps)
+    }
+}
+
+fn read_input(input: &str) -> Result<Vec<Motion>, ParseError> {
+    lines(input)
+        .map(|line| line.parse::<Motion>(line.text))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    const EXAMPLE: &str = "R 4
+U 4
+L 3
+D 1
+R 4
+D 1
+L 5
+R 2
+";
+
+    const LARGER_EXAMPLE: &str = "R 5
+U 8
+L 8
+D 3
+R 17
+D 10
+L 25
+U 20
+";
+
+    #[test]
+    fn render_frames_share_the_example_bounds() {
+        let input = Nine::parse(EXAMPLE).unwrap();
+        let mut sizes = Vec::new();
+        Nine::render(&input, "short-rope", &mut |frame| {
+            sizes.push((frame.width(), frame.height()));
+            Ok(())
+        })
+        .unwrap();
+        assert_eq!(sizes, vec![(6, 5); 9]);
+    }
+
+    #[test]
+    fn animation_steps_through_every_move() {
+        let input = Nine::parse(EXAMPLE).unwrap();
+        let mut animation = Nine::animation(&input, Part::One);
+        assert_eq!(animation.frames(), 25);
+        assert_eq!(
+            animation.draw(3, 4),
+            "motion 1/8: R 4, step 0, tail visited 0\n   \n H \n   \n"
+        );
+        for _ in 0..animation.frames() {
+            animation.step();
+        }
+        assert!(animation.draw(3, 4).starts_with("done, tail visited 13\n"));
+    }
+
+    #[test]
+    fn repl_steps_the_head() {
+        let mut session = Nine::session(Nine::parse(EXAMPLE).unwrap());
+        session.run("knots", &["2"]).unwrap();
+        assert_eq!(
+            session.run("step", &["5"]).unwrap(),
+            "motion 2/8: U 4, step 1, tail visited 4\nhead at 4, -1, tail at 3, 0"
+        );
+        assert_eq!(session.run("show", &["3", "3"]).unwrap(), "   \n H \nT. \n");
+        assert!(session
+            .run("step", &["100"])
+            .unwrap()
+            .starts_with("done, tail visited 13\n"));
+        assert!(session.run("knots", &["1"]).is_err());
+    }
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(Nine::part1(&Nine::parse(EXAMPLE).unwrap()), 13);
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(Nine::part2(&Nine::parse(EXAMPLE).unwrap()), 1);
+    }
+
+    #[test]
+    fn part2_larger_example() {
+        assert_eq!(Nine::part2(&Nine::parse(LARGER_EXAMPLE).unwrap()), 36);
+    }
+
+    #[test]
+    fn wide_paths_only_cost_their_trail() {
+        // A dense map of this path would need ten billion cells
+        let input = Nine::parse("R 100000\nU 100000\n").unwrap();
+        assert_eq!(Nine::part1(&input), 199999);
+        assert_eq!(Nine::part2(&input), 199983);
+    }
+
+    #[test]
+    fn rejects_motions_too_long_to_follow() {
+        let input = Nine::parse("R 1000000\n").unwrap();
+        assert_eq!(Nine::part1(&input), 1_000_000);
+        assert_eq!(Nine::part2(&input), 999_992);
+        let err = Nine::parse("U 1\nR 1000001\n").unwrap_err();
+        assert_eq!(err.line, 2);
+    }
+
+    #[test]
+    fn streaming_matches_parsing() {
+        let inputs = [
+            EXAMPLE.to_string(),
+            LARGER_EXAMPLE.to_string(),
+            DAY.generate(1, 500),
+        ];
+        for input in &inputs {
+            let parsed = Nine::parse(input).unwrap();
+            let streamed = Nine::stream(&mut LineReader::new(&mut input.as_bytes())).unwrap();
+            assert_eq!(streamed, (Nine::part1(&parsed), Nine::part2(&parsed)));
+        }
+    }
+
+    #[test]
+    fn generated_tails_visit_no_more_than_the_head() {
+        for seed in 1..=5 {
+            let motions = Nine::parse(&DAY.generate(seed, 200)).unwrap();
+            let mut head = Point::ORIGIN;
+            let mut visited = HashSet::from([head]);
+            for motion in &motions {
+                for _ in 0..motion.steps {
+                    head += motion.dir.step();
+                    visited.insert(head);
+                }
+            }
+            for tail in [Nine::part1(&motions), Nine::part2(&motions)] {
+                assert!((1..=visited.len()).contains(&tail), "seed {seed}");
+            }
+        }
+    }
+
+    fn motion() -> impl Strategy<Value = Motion> {
+        (prop::sample::select(Dir4::ALL.to_vec()), 0..=MAX_STEPS)
+            .prop_map(|(dir, steps)| Motion { dir, steps })
+    }
+
+    fn rope() -> impl Strategy<Value = Rope> {
+        prop::sample::select(vec![Rope::Head, Rope::Tail, Rope::Both])
+    }
+
+    fn cell() -> impl Strategy<Value = Cell> {
+        prop_oneof![Just(Cell::None), rope().prop_map(Cell::Trail)]
+    }
+
+    proptest! {
+        #[test]
+        fn motion_round_trips(motion in motion()) {
+            prop_assert_eq!(motion.to_string().parse::<Motion>().unwrap(), motion);
+        }
+
+        #[test]
+        fn rope_round_trips(rope in rope()) {
+            prop_assert_eq!(rope.to_string().parse::<Rope>().unwrap(), rope);
+        }
+
+        #[test]
+        fn cell_round_trips(cell in cell()) {
+            prop_assert_eq!(cell.to_string().parse::<Cell>().unwrap(), cell);
+        }
+
+        #[test]
+        fn arbitrary_bytes_never_panic(bytes in prop::collection::vec(any::<u8>(), 0..24)) {
+            let s = String::from_utf8_lossy(&bytes);
+            if let Ok(motion) = s.parse::<Motion>() {
+                prop_assert_eq!(motion.to_string().parse::<Motion>().unwrap(), motion);
+            }
+            prop_assert_eq!(s.parse::<Rope>().is_ok(), ["H", "T", "B"].contains(&&*s));
+            prop_assert_eq!(s.parse::<Cell>().is_ok(), [" ", ".", ",", ";"].contains(&&*s));
+        }
+
+        #[test]
+        fn motion_like_input_never_panics(s in "[UDLRX]( [+-]?[0-9]{0,24}){0,2}") {
+            if let Ok(motion) = s.parse::<Motion>() {
+                prop_assert_eq!(motion.to_string().parse::<Motion>().unwrap(), motion);
+            }
+        }
+    }
+}
@@ -0,0 +1,258 @@
+use anyhow::anyhow;
+use anyhow::Result;
+use aoc_common::arg;
+use aoc_common::lines;
+use aoc_common::unknown;
+use aoc_common::Day;
+use aoc_common::Generator;
+use aoc_common::Line;
+use aoc_common::LineReader;
+use aoc_common::ParseError;
+use aoc_common::Repl;
+use aoc_common::Rng;
+use aoc_common::Session;
+use aoc_common::Solution;
+use aoc_common::Stream;
+use std::cmp::Reverse;
+use std::io;
+use std::io::Write;
+use tracing::debug;
+
+pub const DAY: Day = Day::new::<One>(1, "one")
+    .with_stream::<One>()
+    .with_repl::<One>();
+
+pub struct One;
+
+impl Solution for One {
+    type Input = Vec<u64>;
+    type Output1 = u64;
+    type Output2 = u64;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        read_elves(input)
+    }
+
+    fn part1(elves: &Self::Input) -> u64 {
+        *elves.iter().max().unwrap()
+    }
+
+    fn part2(elves: &Self::Input) -> u64 {
+        let mut elves = elves.clone();
+        elves.sort();
+        elves.into_iter().rev().take(3).sum()
+    }
+}
+
+impl Stream for One {
+    /// Only the three biggest totals so far are kept.
+    fn stream(lines: &mut LineReader<'_>) -> Result<(u64, u64)> {
+        let mut tally = Tally::default();
+        let mut top = [0; 3];
+        let mut elves = 0;
+        while let Some(line) = lines.next_line()? {
+            if let Some(total) = tally.line(&line)? {
+                keep_top(&mut top, total);
+                elves += 1;
+            }
+        }
+        if let Some(total) = tally.finish() {
+            keep_top(&mut top, total);
+            elves += 1;
+        }
+        if elves == 0 {
+            return Err(lines.eof("expected at least one elf").into());
+        }
+        debug!(elves, "streamed elves");
+        Ok((top[0], top.iter().sum()))
+    }
+}
+
+impl Repl for One {
+    const COMMANDS: &'static [(&'static str, &'static str)] = &[
+        (
+            "top [N]",
+            "the N elves carrying the most calories, 3 by default",
+        ),
+        ("elf N", "the calories elf N carries, counting from 1"),
+        ("count", "how many elves there are"),
+    ];
+
+    fn session(elves: Vec<u64>) -> Box<dyn Session> {
+        Box::new(Elves(elves))
+    }
+}
+
+struct Elves(Vec<u64>);
+
+impl Session for Elves {
+    fn run(&mut self, command: &str, args: &[&str]) -> Result<String> {
+        let Elves(elves) = self;
+        match command {
+            "top" => {
+                let n = arg(args, 0, "count", Some(3))?;
+                let mut ranked = elves.iter().enumerate().collect::<Vec<_>>();
+                ranked.sort_by_key(|&(elf, &total)| (Reverse(total), elf));
+                let top = &ranked[..n.min(ranked.len())];
+                let mut out = String::new();
+                for (elf, total) in top {
+                    out += &format!("elf {}: {total}\n", elf + 1);
+                }
+                out += &format!("total {}", top.iter().map(|(_, &total)| total).sum::<u64>());
+                Ok(out)
+            }
+            "elf" => {
+                let elf = arg::<usize>(args, 0, "elf", None)?;
+                match elf.checked_sub(1).and_then(|i| elves.get(i)) {
+                    Some(total) => Ok(total.to_string()),
+                    None => Err(anyhow!("there are elves 1 to {}", elves.len())),
+                }
+            }
+            "count" => Ok(elves.len().to_string()),
+            _ => Err(unknown(command)),
+        }
+    }
+}
+
+impl Generator for One {
+    const UNIT: &'static str = "elves";
+
+    fn generate(rng: &mut Rng, size: usize, out: &mut dyn Write) -> io::Result<()> {
+        for elf in 0..size {
+            if elf > 0 {
+                writeln!(out)?;
+            }
+            for _ in 0..rng.range(1..=15) {
+                writeln!(out, "{}", rng.range(1000..=60000))?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Adds up each elf's calories as the lines go by.
+#[derive(Debug, Default)]
+struct Tally {
+    elf: Option<u64>,
+}
+
+impl Tally {
+    /// Counts `line` towards the current elf, returning the elf's total once
+    /// a blank line ends it.
+    fn line(&mut self, line: &Line) -> Result<Option<u64>, ParseError> {
+        if line.text.trim().is_empty() {
+            Ok(self.elf.take())
+        } else {
+            *self.elf.get_or_insert(0) += line.parse::<u64>(line.text.trim())?;
+            Ok(None)
+        }
+    }
+
+    /// The last elf, which isn't followed by a blank line.
+    fn finish(self) -> Option<u64> {
+        self.elf
+    }
+}
+
+/// Slots `total` into `top`, which is kept biggest first.
+fn keep_top(top: &mut [u64; 3], total: u64) {
+    if let Some(pos) = top.iter().position(|&kept| total > kept) {
+        top[pos..].rotate_right(1);
+        top[pos] = total;
+    }
+}
+
+fn read_elves(input: &str) -> Result<Vec<u64>, ParseError> {
+    let mut tally = Tally::default();
+    let mut elves = Vec::new();
+    for line in lines(input) {
+        elves.extend(tally.line(&line)?);
+    }
+    elves.extend(tally.finish());
+    if elves.is_empty() {
+        return Err(ParseError::eof(input, "expected at least one elf"));
+    }
+    debug!(elves = elves.len(), "read elves");
+    Ok(elves)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "1000
+2000
+3000
+
+4000
+
+5000
+6000
+
+7000
+8000
+9000
+
+10000
+";
+
+    #[test]
+    fn reads_every_elf() {
+        assert_eq!(
+            One::parse(EXAMPLE).unwrap(),
+            vec![6000, 4000, 11000, 24000, 10000]
+        );
+    }
+
+    #[test]
+    fn last_elf_without_trailing_newline() {
+        let elves = One::parse("1000\n\n2000\n3000").unwrap();
+        assert_eq!(elves, vec![1000, 5000]);
+    }
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(One::part1(&One::parse(EXAMPLE).unwrap()), 24000);
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(One::part2(&One::parse(EXAMPLE).unwrap()), 45000);
+    }
+
+    #[test]
+    fn streaming_matches_parsing() {
+        for input in [EXAMPLE.to_string(), DAY.generate(1, 200)] {
+            let parsed = One::parse(&input).unwrap();
+            let streamed = One::stream(&mut LineReader::new(&mut input.as_bytes())).unwrap();
+            assert_eq!(streamed, (One::part1(&parsed), One::part2(&parsed)));
+        }
+        let err = One::stream(&mut LineReader::new(&mut "\n\n".as_bytes())).unwrap_err();
+        assert_eq!(err.downcast::<ParseError>().unwrap().line, 3);
+    }
+
+    #[test]
+    fn repl_ranks_elves() {
+        let mut session = One::session(One::parse(EXAMPLE).unwrap());
+        assert_eq!(
+            session.run("top", &["2"]).unwrap(),
+            "elf 4: 24000\nelf 3: 11000\ntotal 35000"
+        );
+        assert_eq!(session.run("elf", &["2"]).unwrap(), "4000");
+        assert!(session.run("elf", &["6"]).is_err());
+        assert!(session.run("top", &["x"]).is_err());
+    }
+
+    #[test]
+    fn generated_totals_stay_in_range() {
+        for seed in 1..=5 {
+            let elves = One::parse(&DAY.generate(seed, 200)).unwrap();
+            assert_eq!(elves.len(), 200);
+            // Every elf carries 1 to 15 items of 1000 to 60000 calories
+            let most = One::part1(&elves);
+            assert!((1000..=15 * 60000).contains(&most), "seed {seed}: {most}");
+            let top_three = One::part2(&elves);
+            assert!((most + 2 * 1000..=3 * most).contains(&top_three));
+        }
+    }
+}
@@ -0,0 +1,663 @@
+use anyhow::anyhow;
+use anyhow::Error;
+use anyhow::Result;
+use aoc_common::arg;
+use aoc_common::lines;
+use aoc_common::unknown;
+use aoc_common::Day;
+use aoc_common::Generator;
+use aoc_common::Node;
+use aoc_common::Outline;
+use aoc_common::ParseError;
+use aoc_common::Repl;
+use aoc_common::Rng;
+use aoc_common::Session;
+use aoc_common::Solution;
+use std::cmp::Reverse;
+use std::collections::HashMap;
+use std::fmt;
+use std::io;
+use std::io::Write;
+use std::str::FromStr;
+use tracing::debug;
+
+pub const DAY: Day = Day::new::<Seven>(7, "seven")
+    .with_repl::<Seven>()
+    .with_outline::<Seven>();
+
+const TOTAL_SIZE: u64 = 70000000;
+const MIN_SIZE: u64 = 30000000;
+
+pub struct Seven;
+
+impl Solution for Seven {
+    type Input = Traversal;
+    type Output1 = u64;
+    type Output2 = u64;
+    // 2: rejects transcripts that leave the root or overfill the disk
+    // 3: rejects empty directory and file names
+    const VERSION: &'static str = "3";
+
+    /// Rejects transcripts that can't describe a disk: ones that don't start
+    /// at the root or leave it with `cd ..`, and files that don't fit.
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        let mut traversal = Traversal::new();
+        for line in lines(input) {
+            let line_type = line.parse::<LineType>(line.text)?;
+            match &line_type {
+                LineType::Cd(CdType::Root) => {}
+                _ if traversal.path.is_empty() => {
+                    return Err(line.error("the transcript has to start with `$ cd /`"))
+                }
+                LineType::Cd(CdType::Out) if traversal.path.len() == 1 => {
+                    return Err(line.error("`cd ..` at the root"))
+                }
+                LineType::Entry(Entry::File(file)) if file.size > TOTAL_SIZE => {
+                    return Err(
+                        line.error(format!("a file bigger than the whole {TOTAL_SIZE} disk"))
+                    )
+                }
+                _ => {}
+            }
+            traversal.handle_line(line_type);
+        }
+        if traversal.path.is_empty() {
+            return Err(ParseError::eof(input, "expected `$ cd /`"));
+        }
+        traversal.resolve_entries();
+        traversal.sum_subdirs();
+        let used = traversal.root();
+        if used > TOTAL_SIZE {
+            return Err(ParseError::eof(
+                input,
+                format!("the files add up to {used}, more than the whole {TOTAL_SIZE} disk"),
+            ));
+        }
+        debug!(dirs = traversal.tree.len(), "sized directories");
+        Ok(traversal)
+    }
+
+    fn part1(traversal: &Self::Input) -> u64 {
+        let mut size = 0;
+        for value in traversal.tree.values() {
+            if *value <= 100000 {
+                size += *value;
+            }
+        }
+        size
+    }
+
+    fn part2(traversal: &Self::Input) -> u64 {
+        let root = traversal.root();
+        // Parsing made sure everything fits on the disk; with enough space
+        // free already, any directory would do
+        let unused_space = TOTAL_SIZE - root;
+        let needed_size = MIN_SIZE.saturating_sub(unused_space);
+        debug!(
+            used = root,
+            unused = unused_space,
+            needed = needed_size,
+            "freeing space"
+        );
+        // The root itself always frees enough
+        traversal
+            .tree
+            .values()
+            .copied()
+            .filter(|&val| val >= needed_size)
+            .min()
+            .unwrap_or(root)
+    }
+}
+
+impl Repl for Seven {
+    const COMMANDS: &'static [(&'static str, &'static str)] = &[
+        ("du PATH", "the total size of directory PATH, like /a/e"),
+        (
+            "ls [PATH]",
+            "the directories in PATH with their sizes, / by default",
+        ),
+    ];
+
+    fn session(traversal: Traversal) -> Box<dyn Session> {
+        Box::new(traversal)
+    }
+}
+
+impl Session for Traversal {
+    fn run(&mut self, command: &str, args: &[&str]) -> Result<String> {
+        match command {
+            "du" => {
+                let path = arg::<String>(args, 0, "path", None)?;
+                Ok(self.size(&path)?.to_string())
+            }
+            "ls" => {
+                let path = arg(args, 0, "path", Some("/".to_string()))?;
+                self.size(&path)?;
+                let parent = format!("{}/", tree_key(&path));
+                let mut dirs = self
+                    .tree
+                    .iter()
+                    .filter_map(|(dir, &size)| Some((dir.strip_prefix(&parent)?, size)))
+                    .filter(|(name, _)| !name.contains('/'))
+                    .collect::<Vec<_>>();
+                dirs.sort();
+                Ok(dirs
+                    .iter()
+                    .map(|(name, size)| format!("dir {name} ({size})"))
+                    .collect::<Vec<_>>()
+                    .join("\n"))
+            }
+            _ => Err(unknown(command)),
+        }
+    }
+}
+
+/// `/a/e` is kept as `/a/e` and `/` as the empty string.
+impl Outline for Seven {
+    fn outline(traversal: &Traversal) -> Node {
+        traversal.node("", "/")
+    }
+}
+
+fn tree_key(path: &str) -> &str {
+    path.trim_end_matches('/')
+}
+
+impl Generator for Seven {
+    const UNIT: &'static str = "directories";
+
+    fn generate(rng: &mut Rng, size: usize, out: &mut dyn Write) -> io::Result<()> {
+        let mut children = vec![Vec::new(); size.max(1)];
+        for dir in 1..children.len() {
+            let parent = rng.below(dir);
+            children[parent].push(dir);
+        }
+        // File sizes are weights scaled so the whole disk lands between the
+        // 40000000 part 2 needs and the 70000000 total
+        let mut files = children
+            .iter()
+            .map(|_| {
+                (0..rng.below(4))
+                    .map(|_| rng.range(1..=1000) as u64)
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+        if files[0].is_empty() {
+            files[0].push(1);
+        }
+        let target = rng.range(40_000_001..=60_000_000) as u64;
+        scale_files(&mut files, target);
+        writeln!(out, "$ cd /")?;
+        write_dir(rng, out, 0, &children, &files)
+    }
+}
+
+/// Scales the file size weights so they add up to at least `target`, which
+/// is exact unless there are so many files that each needs a byte anyway.
+fn scale_files(files: &mut [Vec<u64>], target: u64) {
+    let weights = files.iter().flatten().sum::<u64>();
+    for size in files.iter_mut().flatten() {
+        *size = (*size * target / weights).max(1);
+    }
+    // Rounding down loses up to a byte a file, which could leave the disk
+    // short of what part 2 needs; the root's first file makes it up
+    let total = files.iter().flatten().sum::<u64>();
+    files[0][0] += target.saturating_sub(total);
+}
+
+fn write_dir(
+    rng: &mut Rng,
+    out: &mut dyn Write,
+    dir: usize,
+    children: &[Vec<usize>],
+    files: &[Vec<u64>],
+) -> io::Result<()> {
+    let names = children[dir]
+        .iter()
+        .map(|&child| format!("{}{child}", word(rng)))
+        .collect::<Vec<_>>();
+    let mut entries = names
+        .iter()
+        .map(|name| format!("dir {name}"))
+        .chain(files[dir].iter().map(|size| {
+            let ext = rng.pick(&["", ".txt", ".dat", ".log", ".lst"]);
+            format!("{size} {}{ext}", word(rng))
+        }))
+        .collect::<Vec<_>>();
+    rng.shuffle(&mut entries);
+    writeln!(out, "$ ls")?;
+    for entry in entries {
+        writeln!(out, "{entry}")?;
+    }
+    for (&child, name) in children[dir].iter().zip(&names) {
+        writeln!(out, "$ cd {name}")?;
+        write_dir(rng, out, child, children, files)?;
+        writeln!(out, "$ cd ..")?;
+    }
+    Ok(())
+}
+
+fn word(rng: &mut Rng) -> String {
+    (0..rng.range(1..=6))
+        .map(|_| (b'a' + rng.below(26) as u8) as char)
+        .collect()
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Entry {
+    File(File),
+    Dir(String),
+}
+
+#[derive(Debug)]
+pub struct Traversal {
+    path: Vec<String>,
+    entries: Vec<Entry>,
+    tree: HashMap<String, u64>,
+}
+
+impl Traversal {
+    fn new() -> Self {
+        Self {
+            path: Vec::new(),
+            entries: Vec::new(),
+            tree: HashMap::new(),
+        }
+    }
+
+    fn handle_line(&mut self, line_type: LineType) {
+        match line_type {
+            LineType::Cd(cd_type) => match cd_type {
+                CdType::Dir(dir) => self.push_dir(dir),
+                CdType::Out => self.pop_dir(),
+                CdType::Root => self.go_root(),
+            },
+            LineType::Ls => {}
+            LineType::Entry(entry_type) => self.add_entry(entry_type),
+        }
+    }
+
+    fn push_dir(&mut self, dir: String) {
+        self.resolve_entries();
+        self.path.push(dir);
+    }
+
+    fn pop_dir(&mut self) {
+        self.resolve_entries();
+        self.path.pop();
+    }
+
+    fn go_root(&mut self) {
+        self.resolve_entries();
+        self.path.clear();
+        self.push_dir("".into());
+    }
+
+    fn resolve_entries(&mut self) {
+        let mut sum = 0;
+        for entry in &self.entries {
+            match entry {
+                Entry::File(file) => {
+                    sum += file.size;
+                }
+                Entry::Dir(dir) => {
+                    let mut p = self.path.clone();
+                    p.push(dir.clone());
+                    self.tree.entry(p.join("/")).or_insert(0);
+                }
+            }
+        }
+        if self.path.is_empty() {
+            return;
+        }
+        let key = self.path.join("/");
+        *self.tree.entry(key).or_insert(0) += sum;
+        self.entries = Vec::new();
+    }
+
+    /// The total size of everything, which parsing makes sure is known.
+    fn root(&self) -> u64 {
+        self.tree[""]
+    }
+
+    /// The total size of the directory at `path`.
+    fn size(&self, path: &str) -> Result<u64> {
+        if !path.starts_with('/') {
+            return Err(anyhow!("paths start at /, like /a/e"));
+        }
+        self.tree
+            .get(tree_key(path))
+            .copied()
+            .ok_or_else(|| anyhow!("no directory {path}"))
+    }
+
+    /// The directory at `key` in `tree`, labelled `name`, with its
+    /// subdirectories in name order.
+    fn node(&self, key: &str, name: &str) -> Node {
+        let parent = format!("{key}/");
+        let mut children = self
+            .tree
+            .keys()
+            .filter_map(|dir| dir.strip_prefix(&parent))
+            .filter(|name| !name.contains('/'))
+            .collect::<Vec<_>>();
+        children.sort();
+        Node {
+            label: format!("{name} ({})", self.tree[key]),
+            children: children
+                .into_iter()
+                .map(|child| self.node(&format!("{parent}{child}"), child))
+                .collect(),
+        }
+    }
+
+    fn add_entry(&mut self, entry: Entry) {
+        self.entries.push(entry);
+    }
+
+    fn sum_subdirs(&mut self) {
+        let mut dirs = self.tree.keys().map(|x| x.to_string()).collect::<Vec<_>>();
+        dirs.sort_by_key(|x| Reverse(x.len()));
+        for (i, child) in dirs.iter().enumerate() {
+            for parent in &dirs[i..] {
+                let ccount = child.chars().filter(|&c| c == '/').count();
+                let pcount = parent.chars().filter(|&c| c == '/').count();
+                if pcount + 1 == ccount && child.starts_with(&format!("{parent}/")) {
+                    let cval = *self.tree.get(child).unwrap();
+                    *self.tree.get_mut(parent).unwrap() += cval;
+                }
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum CdType {
+    Dir(String),
+    Out,
+    Root,
+}
+
+impl FromStr for CdType {
+    type Err = Error;
+    fn from_str(s: &str) -> Result<Self> {
+        let out = match s {
+            "cd /" => CdType::Root,
+            "cd .." => CdType::Out,
+            other => {
+                let sp = other.split(" ").collect::<Vec<_>>();
+                if let [action, dir] = &sp[..] {
+                    if action.trim() != "cd" {
+                        return Err(anyhow!("Invalid cd action: {other}"));
+                    } else if dir.is_empty() {
+                        return Err(anyhow!("missing directory name"));
+                    } else {
+                        CdType::Dir(dir.to_string())
+                    }
+                } else {
+                    return Err(anyhow!("Invalid cd action: {other}"));
+                }
+            }
+        };
+        Ok(out)
+    }
+}
+
+impl fmt::Display for CdType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CdType::Dir(dir) => write!(f, "cd {dir}"),
+            CdType::Out => write!(f, "cd .."),
+            CdType::Root => write!(f, "cd /"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct File {
+    size: u64,
+    name: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum LineType {
+    Cd(CdType),
+    Ls,
+    Entry(Entry),
+}
+
+impl FromStr for LineType {
+    type Err = Error;
+    fn from_str(line: &str) -> Result<Self> {
+        if line.starts_with("$ cd") {
+            Ok(LineType::Cd(CdType::from_str(&line[2..])?))
+        } else if let Some(dir) = line.strip_prefix("dir ") {
+            if dir.is_empty() {
+                return Err(anyhow!("missing directory name"));
+            }
+            Ok(LineType::Entry(Entry::Dir(dir.to_string())))
+        } else if line.starts_with("$ ls") {
+            Ok(LineType::Ls)
+        } else {
+            if let [size, name] = &line.split(" ").collect::<Vec<_>>()[..] {
+                if name.is_empty() {
+                    return Err(anyhow!("missing file name"));
+                }
+                Ok(LineType::Entry(Entry::File(File {
+                    size: size
+                        .parse::<u64>()
+                        .map_err(|_| anyhow!("Invalid u64: {size}"))?,
+                    name: name.to_string(),
+                })))
+            } else {
+                Err(anyhow!("invalid line type: {line}"))
+            }
+        }
+    }
+}
+
+/// Formats a line the way it appears in the terminal transcript.
+impl fmt::Display for LineType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            LineType::Cd(cd_type) => write!(f, "$ {cd_type}"),
+            LineType::Ls => write!(f, "$ ls"),
+            LineType::Entry(Entry::Dir(dir)) => write!(f, "dir {dir}"),
+            LineType::Entry(Entry::File(file)) => write!(f, "{} {}", file.size, file.name),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    const EXAMPLE: &str = "$ cd /
+$ ls
+dir a
+14848514 b.txt
+8504156 c.dat
+dir d
+$ cd a
+$ ls
+dir e
+29116 f
+2557 g
+62596 h.lst
+$ cd e
+$ ls
+584 i
+$ cd ..
+$ cd ..
+$ cd d
+$ ls
+4060174 j
+8033020 d.log
+5626152 d.ext
+7214296 k
+";
+
+    #[test]
+    fn rejects_transcripts_that_cannot_be_a_disk() {
+        let line = |input: &str| Seven::parse(input).unwrap_err().line;
+        assert_eq!(line("$ ls\n1 a\n"), 1);
+        assert_eq!(line("$ cd /\n$ cd ..\n"), 2);
+        assert_eq!(line("$ cd /\n$ ls\n70000001 a\n"), 3);
+        assert_eq!(line("$ cd /\n$ ls\n40000000 a\n40000000 b\n"), 5);
+        assert_eq!(line(""), 1);
+    }
+
+    #[test]
+    fn rejects_empty_names() {
+        let err = |input: &str| {
+            let err = Seven::parse(input).unwrap_err();
+            (err.line, err.to_string())
+        };
+        let (line, msg) = err("$ cd /\n$ cd \n");
+        assert_eq!(line, 2);
+        assert!(msg.contains("missing directory name"), "{msg}");
+        let (line, msg) = err("$ cd /\n$ ls\ndir \n");
+        assert_eq!(line, 3);
+        assert!(msg.contains("missing directory name"), "{msg}");
+        let (line, msg) = err("$ cd /\n$ ls\n12 \n");
+        assert_eq!(line, 3);
+        assert!(msg.contains("missing file name"), "{msg}");
+    }
+
+    #[test]
+    fn part2_with_space_to_spare() {
+        let traversal = Seven::parse("$ cd /\n$ ls\ndir a\n5 b\n$ cd a\n$ ls\n3 c\n").unwrap();
+        assert_eq!(Seven::part2(&traversal), 3);
+    }
+
+    #[test]
+    fn sums_directories() {
+        let traversal = Seven::parse(EXAMPLE).unwrap();
+        assert_eq!(traversal.tree["/a/e"], 584);
+        assert_eq!(traversal.tree["/a"], 94853);
+        assert_eq!(traversal.tree["/d"], 24933642);
+        assert_eq!(traversal.tree[""], 48381165);
+    }
+
+    #[test]
+    fn repl_sizes_directories() {
+        let mut session = Seven::session(Seven::parse(EXAMPLE).unwrap());
+        assert_eq!(session.run("du", &["/a/e"]).unwrap(), "584");
+        assert_eq!(session.run("du", &["/"]).unwrap(), "48381165");
+        assert_eq!(
+            session.run("ls", &[]).unwrap(),
+            "dir a (94853)\ndir d (24933642)"
+        );
+        assert_eq!(session.run("ls", &["/a/"]).unwrap(), "dir e (584)");
+        assert!(session.run("du", &["/x"]).is_err());
+        assert!(session.run("du", &["a"]).is_err());
+    }
+
+    #[test]
+    fn outline_nests_directories() {
+        let root = Seven::outline(&Seven::parse(EXAMPLE).unwrap());
+        assert_eq!(root.label, "/ (48381165)");
+        let labels = |node: &Node| {
+            node.children
+                .iter()
+                .map(|child| child.label.clone())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(labels(&root), ["a (94853)", "d (24933642)"]);
+        assert_eq!(labels(&root.children[0]), ["e (584)"]);
+        assert!(root.children[0].children[0].children.is_empty());
+    }
+
+    #[test]
+    fn scaled_files_make_the_target() {
+        // Every one of these rounds down
+        let mut files = vec![vec![3; 10]; 100];
+        scale_files(&mut files, 40_000_001);
+        assert_eq!(files.iter().flatten().sum::<u64>(), 40_000_001);
+    }
+
+    #[test]
+    fn generated_disks_fill_up_enough() {
+        for seed in 1..=2 {
+            let traversal = Seven::parse(&DAY.generate(seed, 1000)).unwrap();
+            let used = traversal.root();
+            assert!(used > TOTAL_SIZE - MIN_SIZE, "seed {seed}: {used}");
+            let freed = Seven::part2(&traversal);
+            assert!(traversal.tree.values().any(|&size| size == freed));
+            assert!(TOTAL_SIZE - used + freed >= MIN_SIZE, "seed {seed}");
+        }
+    }
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(Seven::part1(&Seven::parse(EXAMPLE).unwrap()), 95437);
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(Seven::part2(&Seven::parse(EXAMPLE).unwrap()), 24933642);
+    }
+
+    #[test]
+    fn similar_names_are_not_nested() {
+        // `/b/ab` must count towards `/b`, not `/a`
+        let input = "$ cd /\n$ ls\ndir a\ndir b\n$ cd b\n$ ls\ndir ab\n$ cd ab\n$ ls\n10 f\n";
+        let traversal = Seven::parse(input).unwrap();
+        assert_eq!(traversal.tree["/a"], 0);
+        assert_eq!(traversal.tree["/b"], 10);
+    }
+
+    // Names never contain spaces, and `..` and `/` are reserved by `cd`
+    const NAME: &str = "[a-z][a-z.]{0,9}";
+
+    fn cd_type() -> impl Strategy<Value = CdType> {
+        prop_oneof![
+            NAME.prop_map(CdType::Dir),
+            Just(CdType::Out),
+            Just(CdType::Root),
+        ]
+    }
+
+    fn line_type() -> impl Strategy<Value = LineType> {
+        prop_oneof![
+            cd_type().prop_map(LineType::Cd),
+            Just(LineType::Ls),
+            NAME.prop_map(|dir| LineType::Entry(Entry::Dir(dir))),
+            (any::<u64>(), NAME)
+                .prop_map(|(size, name)| LineType::Entry(Entry::File(File { size, name }))),
+        ]
+    }
+
+    proptest! {
+        #[test]
+        fn cd_type_round_trips(cd_type in cd_type()) {
+            prop_assert_eq!(cd_type.to_string().parse::<CdType>().unwrap(), cd_type);
+        }
+
+        #[test]
+        fn line_type_round_trips(line_type in line_type()) {
+            prop_assert_eq!(line_type.to_string().parse::<LineType>().unwrap(), line_type);
+        }
+
+        #[test]
+        fn arbitrary_bytes_never_panic(bytes in prop::collection::vec(any::<u8>(), 0..32)) {
+            let s = String::from_utf8_lossy(&bytes);
+            // Anything accepted must come back out the same way
+            if let Ok(cd_type) = s.parse::<CdType>() {
+                prop_assert_eq!(cd_type.to_string().parse::<CdType>().unwrap(), cd_type);
+            }
+            if let Ok(line_type) = s.parse::<LineType>() {
+                prop_assert_eq!(line_type.to_string().parse::<LineType>().unwrap(), line_type);
+            }
+        }
+
+        #[test]
+        fn transcript_lines_never_panic(s in "(\\$ (cd|ls)|dir|[0-9]+)( \\PC{0,8}){0,3}") {
+            if let Ok(line_type) = s.parse::<LineType>() {
+                prop_assert_eq!(line_type.to_string().parse::<LineType>().unwrap(), line_type);
+            }
+        }
+    }
+}
@@ -0,0 +1,163 @@
+use aoc_common::lines;
+use aoc_common::Day;
+use aoc_common::Generator;
+use aoc_common::ParseError;
+use aoc_common::Rng;
+use aoc_common::Solution;
+use std::collections::HashSet;
+use std::io;
+use std::io::Write;
+use tracing::debug;
+
+pub const DAY: Day = Day::new::<Six>(6, "six");
+
+pub struct Six;
+
+impl Solution for Six {
+    type Input = Markers;
+    type Output1 = usize;
+    type Output2 = usize;
+    // 2: rejects datastreams without a marker instead of answering 0
+    const VERSION: &'static str = "2";
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        read_input(input)
+    }
+
+    fn part1(input: &Self::Input) -> usize {
+        input.packet
+    }
+
+    fn part2(input: &Self::Input) -> usize {
+        input.message
+    }
+}
+
+/// Where the first start-of-packet and start-of-message markers end.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Markers {
+    pub packet: usize,
+    pub message: usize,
+}
+
+impl Generator for Six {
+    const UNIT: &'static str =
+        "characters, with markers ending at a third and two thirds of the way";
+
+    fn generate(rng: &mut Rng, size: usize, out: &mut dyn Write) -> io::Result<()> {
+        let size = size.max(32);
+        let (packet, message) = marker_offsets(size);
+        let mut stream = Vec::with_capacity(size + 1);
+        // Runs of `a` and `b` never hold four different characters, and the
+        // doubled letters stop the planted markers ending any earlier
+        let mut filler = |stream: &mut Vec<u8>, until: usize| {
+            while stream.len() < until {
+                stream.push(rng.pick(b"ab"));
+            }
+        };
+        filler(&mut stream, packet - 5);
+        stream.extend_from_slice(b"ddefg");
+        filler(&mut stream, message - 16);
+        stream.extend_from_slice(b"iiijklmnopqrstuv");
+        while stream.len() < size {
+            stream.push(b'a' + rng.below(26) as u8);
+        }
+        stream.push(b'\n');
+        out.write_all(&stream)
+    }
+}
+
+/// Where the generated start-of-packet and start-of-message markers end.
+fn marker_offsets(size: usize) -> (usize, usize) {
+    let packet = size / 3;
+    (packet, (2 * size / 3).max(packet + 16))
+}
+
+/// Where the first run of `num` different characters ends, if there is one.
+fn first_unique_chars(input: &[char], num: usize) -> Option<usize> {
+    let end = input
+        .windows(num)
+        .position(|window| window.iter().collect::<HashSet<_>>().len() == num)
+        .map(|i| i + num);
+    debug!(len = num, ?end, "looked for marker");
+    end
+}
+
+fn read_input(input: &str) -> Result<Markers, ParseError> {
+    let line = lines(input)
+        .next()
+        .ok_or_else(|| ParseError::eof(input, "missing datastream"))?;
+    let stream = line.text.trim_end();
+    if let Some(pos) = stream.find(|c: char| !c.is_ascii_lowercase()) {
+        return Err(line.error_at(&stream[pos..pos + 1], "expected a lowercase letter"));
+    }
+    let chars = stream.chars().collect::<Vec<_>>();
+    let marker = |len, name| {
+        first_unique_chars(&chars, len)
+            .ok_or_else(|| line.error(format!("no start-of-{name} marker found")))
+    };
+    Ok(Markers {
+        packet: marker(4, "packet")?,
+        message: marker(14, "message")?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLES: &[(&str, usize, usize)] = &[
+        ("mjqjpqmgbljsphdztnvjfqwrcgsmlb", 7, 19),
+        ("bvwbjplbgvbhsrlpgdmjqwftvncz", 5, 23),
+        ("nppdvjthqldpwncqszvftbrmjlhg", 6, 23),
+        ("nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg", 10, 29),
+        ("zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw", 11, 26),
+    ];
+
+    #[test]
+    fn part1_examples() {
+        for (stream, part1, _) in EXAMPLES {
+            assert_eq!(Six::part1(&Six::parse(stream).unwrap()), *part1, "{stream}");
+        }
+    }
+
+    #[test]
+    fn part2_examples() {
+        for (stream, _, part2) in EXAMPLES {
+            assert_eq!(Six::part2(&Six::parse(stream).unwrap()), *part2, "{stream}");
+        }
+    }
+
+    #[test]
+    fn marker_in_final_window() {
+        let chars = |s: &str| s.chars().collect::<Vec<_>>();
+        assert_eq!(first_unique_chars(&chars("aaabcd"), 4), Some(6));
+        assert_eq!(first_unique_chars(&chars("abc"), 4), None);
+    }
+
+    #[test]
+    fn rejects_streams_without_markers() {
+        let err = Six::parse("abcabc\n").unwrap_err();
+        assert_eq!(err.line, 1);
+        assert!(
+            err.to_string().contains("no start-of-packet marker"),
+            "{err}"
+        );
+        // A packet marker alone still leaves part 2 without an answer
+        let err = Six::parse("abcd\n").unwrap_err();
+        assert!(
+            err.to_string().contains("no start-of-message marker"),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn generated_markers_at_planted_offsets() {
+        for size in [1, 100, 1000] {
+            let input = Six::parse(&DAY.generate(size as u64, size)).unwrap();
+            let (packet, message) = marker_offsets(size.max(32));
+            assert_eq!(Six::part1(&input), packet);
+            assert_eq!(Six::part2(&input), message);
+        }
+    }
+}
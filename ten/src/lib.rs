@@ -0,0 +1,625 @@
+use anyhow::anyhow;
+use anyhow::Error;
+use anyhow::Result;
+use aoc_common::arg;
+use aoc_common::lines;
+use aoc_common::unknown;
+use aoc_common::Animate;
+use aoc_common::Animation;
+use aoc_common::Answer;
+use aoc_common::Day;
+use aoc_common::Frame;
+use aoc_common::Frames;
+use aoc_common::Generator;
+use aoc_common::Grid;
+use aoc_common::LineReader;
+use aoc_common::ParseError;
+use aoc_common::Part;
+use aoc_common::Render;
+use aoc_common::Repl;
+use aoc_common::Rgb;
+use aoc_common::Rng;
+use aoc_common::Session;
+use aoc_common::Solution;
+use aoc_common::Stream;
+use std::fmt;
+use std::io;
+use std::io::Write;
+use std::str::FromStr;
+use tracing::debug;
+use tracing::trace;
+
+pub const DAY: Day = Day::new::<Ten>(10, "ten")
+    .with_render::<Ten>()
+    .with_animate::<Ten>()
+    .with_stream::<Ten>()
+    .with_repl::<Ten>();
+
+pub struct Ten;
+
+impl Solution for Ten {
+    type Input = Vec<Op>;
+    type Output1 = isize;
+    type Output2 = Screen;
+    // 2: rejects addx operands outside 32 bits
+    const VERSION: &'static str = "2";
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        read_input(input)
+    }
+
+    fn part1(input: &Self::Input) -> isize {
+        Computer::run(input).result
+    }
+
+    fn part2(input: &Self::Input) -> Screen {
+        Computer::run(input).screen
+    }
+}
+
+impl Stream for Ten {
+    fn stream(lines: &mut LineReader<'_>) -> Result<(isize, Screen)> {
+        let mut comp = Computer::new();
+        while let Some(line) = lines.next_line()? {
+            comp.do_op(line.parse::<Op>(line.text)?);
+        }
+        Ok((comp.result, comp.screen))
+    }
+}
+
+impl Generator for Ten {
+    const UNIT: &'static str = "instructions";
+
+    fn generate(rng: &mut Rng, size: usize, out: &mut dyn Write) -> io::Result<()> {
+        let mut x = 1;
+        for _ in 0..size {
+            if rng.chance(30) {
+                writeln!(out, "noop")?;
+                continue;
+            }
+            // Keeps the sprite roughly on screen, like real programs do
+            let mut val = rng.range(-10..=10);
+            if !(-5..=45).contains(&(x + val)) {
+                val = -val;
+            }
+            x += val;
+            writeln!(out, "addx {val}")?;
+        }
+        Ok(())
+    }
+}
+
+impl Render for Ten {
+    const VIEWS: &'static [&'static str] = &["crt"];
+
+    /// Draws the CRT after every cycle until the screen is full: lit pixels
+    /// green, dark ones dim, the pixel just drawn white and, on an extra row
+    /// under the screen, the sprite in amber.
+    fn render(input: &Self::Input, _view: &str, frames: Frames<'_>) -> io::Result<()> {
+        let mut comp = Computer::new();
+        for &op in input {
+            for _ in 0..op.cycles() {
+                if comp.screen.y == HEIGHT {
+                    return Ok(());
+                }
+                let beam = (comp.screen.x, comp.screen.y);
+                comp.inc_cycle();
+                frames(&comp.frame(beam))?;
+            }
+            comp.finish_op(op);
+        }
+        Ok(())
+    }
+}
+
+impl Animate for Ten {
+    fn animation(input: &Self::Input, _part: Part) -> Box<dyn Animation + '_> {
+        let cycles = input.iter().map(|op| op.cycles()).sum::<usize>();
+        Box::new(CrtAnimation {
+            ops: input,
+            len: cycles.min(WIDTH * HEIGHT) + 1,
+            program: Program::new(),
+        })
+    }
+}
+
+/// A program being run one cycle at a time.
+#[derive(Debug)]
+struct Program {
+    comp: Computer,
+    /// The op being run and how many of its cycles are done.
+    next: usize,
+    done: usize,
+}
+
+impl Program {
+    fn new() -> Self {
+        Self {
+            comp: Computer::new(),
+            next: 0,
+            done: 0,
+        }
+    }
+
+    /// Runs the next cycle of `ops`, returning false once they're done.
+    fn step(&mut self, ops: &[Op]) -> bool {
+        let Some(&op) = ops.get(self.next) else {
+            return false;
+        };
+        self.comp.inc_cycle();
+        self.done += 1;
+        if self.done == op.cycles() {
+            self.comp.finish_op(op);
+            self.next += 1;
+            self.done = 0;
+        }
+        true
+    }
+
+    fn status(&self) -> String {
+        let comp = &self.comp;
+        format!(
+            "cycle {}, X = {}, signal strength so far {}",
+            comp.cycle, comp.x, comp.result
+        )
+    }
+
+    /// The screen with `@` where the beam draws next and, under it, `=`
+    /// where the sprite is.
+    fn draw(&self) -> String {
+        let comp = &self.comp;
+        let mut out = String::new();
+        for (y, row) in comp.screen.pixels.rows().enumerate() {
+            for (x, &pixel) in row.iter().enumerate() {
+                let beam = (x, y) == (comp.screen.x, comp.screen.y);
+                out.push(if beam { '@' } else { pixel });
+            }
+            out.push('\n');
+        }
+        for x in 0..WIDTH as isize {
+            out.push(if (comp.x - 1..=comp.x + 1).contains(&x) {
+                '='
+            } else {
+                ' '
+            });
+        }
+        out
+    }
+}
+
+/// Draws one CRT pixel per frame.
+struct CrtAnimation<'a> {
+    ops: &'a [Op],
+    len: usize,
+    program: Program,
+}
+
+impl Animation for CrtAnimation<'_> {
+    fn frames(&self) -> usize {
+        self.len
+    }
+
+    fn reset(&mut self) {
+        self.program = Program::new();
+    }
+
+    fn step(&mut self) {
+        if self.program.comp.screen.y < HEIGHT {
+            self.program.step(self.ops);
+        }
+    }
+
+    fn draw(&self, _width: usize, _height: usize) -> String {
+        format!("{}\n{}", self.program.status(), self.program.draw())
+    }
+}
+
+impl Repl for Ten {
+    const COMMANDS: &'static [(&'static str, &'static str)] = &[
+        ("step [N]", "run N cycles, 1 by default"),
+        ("show", "draw the screen, the beam and the sprite"),
+        ("reset", "start the program over"),
+    ];
+
+    fn session(ops: Vec<Op>) -> Box<dyn Session> {
+        Box::new(CpuSession {
+            ops,
+            program: Program::new(),
+        })
+    }
+}
+
+struct CpuSession {
+    ops: Vec<Op>,
+    program: Program,
+}
+
+impl Session for CpuSession {
+    fn run(&mut self, command: &str, args: &[&str]) -> Result<String> {
+        match command {
+            "step" => {
+                for _ in 0..arg::<usize>(args, 0, "count", Some(1))? {
+                    if !self.program.step(&self.ops) {
+                        return Ok(format!("{}, program finished", self.program.status()));
+                    }
+                }
+                Ok(self.program.status())
+            }
+            "show" => Ok(format!(
+                "{}\n{}",
+                self.program.status(),
+                self.program.draw()
+            )),
+            "reset" => {
+                self.program = Program::new();
+                Ok(self.program.status())
+            }
+            _ => Err(unknown(command)),
+        }
+    }
+}
+
+const WIDTH: usize = 40;
+const HEIGHT: usize = 6;
+
+#[derive(Debug)]
+pub struct Screen {
+    x: usize,
+    y: usize,
+    pixels: Grid<char>,
+}
+
+impl Screen {
+    fn new() -> Self {
+        Self {
+            x: 0,
+            y: 0,
+            pixels: Grid::new(WIDTH, HEIGHT, ' '),
+        }
+    }
+    fn inc_x(&mut self, sprite_center: isize) {
+        // Cycles past the last row aren't drawn
+        if self.y == HEIGHT {
+            return;
+        }
+        if ((sprite_center - 1)..=(sprite_center + 1)).contains(&(self.x as isize)) {
+            self.pixels[(self.x, self.y)] = '#';
+        } else {
+            self.pixels[(self.x, self.y)] = '.';
+        }
+        self.x += 1;
+        if self.x == WIDTH {
+            self.x = 0;
+            self.y += 1;
+        }
+    }
+}
+
+impl From<Screen> for Answer {
+    fn from(screen: Screen) -> Self {
+        Answer::Rows(
+            screen
+                .pixels
+                .rows()
+                .map(|row| row.iter().collect())
+                .collect(),
+        )
+    }
+}
+
+impl fmt::Display for Screen {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "{}", self.pixels)
+    }
+}
+
+#[derive(Debug)]
+struct Computer {
+    cycle: usize,
+    x: isize,
+    result: isize,
+    screen: Screen,
+}
+
+impl Computer {
+    fn new() -> Self {
+        Self {
+            cycle: 1,
+            x: 1,
+            result: 0,
+            screen: Screen::new(),
+        }
+    }
+
+    fn run(ops: &[Op]) -> Self {
+        let mut comp = Computer::new();
+        for op in ops {
+            comp.do_op(*op);
+        }
+        comp
+    }
+
+    fn do_op(&mut self, op: Op) {
+        for _ in 0..op.cycles() {
+            self.inc_cycle();
+        }
+        self.finish_op(op);
+    }
+
+    // Ops only change the register once all their cycles are done
+    fn finish_op(&mut self, op: Op) {
+        if let Op::AddX(val) = op {
+            self.inc_x(val);
+        }
+    }
+
+    fn frame(&self, beam: (usize, usize)) -> Frame {
+        let mut frame = Grid::new(WIDTH, HEIGHT + 1, Rgb::BLACK);
+        for ((x, y), &pixel) in self.screen.pixels.iter() {
+            frame[(x, y)] = match pixel {
+                '#' => Rgb(90, 255, 120),
+                '.' => Rgb(15, 45, 25),
+                _ => Rgb::BLACK,
+            };
+        }
+        frame[beam] = Rgb::WHITE;
+        for x in self.x - 1..=self.x + 1 {
+            if (0..WIDTH as isize).contains(&x) {
+                frame[(x as usize, HEIGHT)] = Rgb(255, 170, 0);
+            }
+        }
+        frame
+    }
+    fn inc_x(&mut self, val: isize) {
+        self.x += val;
+    }
+    fn inc_cycle(&mut self) {
+        trace!(cycle = self.cycle, x = self.x, "cycle");
+        self.check_cycle();
+        self.screen.inc_x(self.x);
+        self.cycle += 1;
+    }
+
+    fn check_cycle(&mut self) {
+        match self.cycle {
+            20 | 60 | 100 | 140 | 180 | 220 => {
+                let strength = self.cycle as isize * self.x;
+                self.result += strength;
+                debug!(
+                    cycle = self.cycle,
+                    x = self.x,
+                    strength,
+                    result = self.result,
+                    "sampled signal strength"
+                );
+            }
+            _ => {}
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Op {
+    AddX(isize),
+    Noop,
+}
+
+impl Op {
+    fn cycles(self) -> usize {
+        match self {
+            Op::Noop => 1,
+            Op::AddX(_) => 2,
+        }
+    }
+}
+
+impl FromStr for Op {
+    type Err = Error;
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "noop" => Ok(Self::Noop),
+            other => {
+                if let [op, val] = &other.split(' ').collect::<Vec<_>>()[..] {
+                    match *op {
+                        // Kept to 32 bits so X and the signal strengths it's
+                        // multiplied into can't overflow short of 2^32 adds
+                        "addx" => Ok(Self::AddX(
+                            val.parse::<i32>()
+                                .map_err(|_| anyhow!("invalid addx value: {val}"))?
+                                as isize,
+                        )),
+                        _ => Err(anyhow!("invalid op: {op}")),
+                    }
+                } else {
+                    Err(anyhow!("invalid op: {other}"))
+                }
+            }
+        }
+    }
+}
+
+impl fmt::Display for Op {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Op::AddX(val) => write!(f, "addx {val}"),
+            Op::Noop => write!(f, "noop"),
+        }
+    }
+}
+
+fn read_input(input: &str) -> Result<Vec<Op>, ParseError> {
+    lines(input)
+        .map(|line| line.parse::<Op>(line.text))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    const EXAMPLE: &str = include_str!("../input2");
+
+    #[test]
+    fn render_stops_when_the_screen_is_full() {
+        let input = Ten::parse(EXAMPLE).unwrap();
+        let mut frames = Vec::new();
+        Ten::render(&input, "crt", &mut |frame| {
+            frames.push(frame.clone());
+            Ok(())
+        })
+        .unwrap();
+        assert_eq!(frames.len(), WIDTH * HEIGHT);
+        assert_eq!(frames[0][(0, 0)], Rgb::WHITE);
+        // The example's first row starts `##..##..`
+        let last = frames.last().unwrap();
+        assert_eq!(last[(1, 0)], Rgb(90, 255, 120));
+        assert_eq!(last[(2, 0)], Rgb(15, 45, 25));
+    }
+
+    #[test]
+    fn animation_draws_the_whole_screen() {
+        let input = Ten::parse(EXAMPLE).unwrap();
+        let mut animation = Ten::animation(&input, Part::Both);
+        assert_eq!(animation.frames(), WIDTH * HEIGHT + 1);
+        assert!(animation.draw(80, 24).contains("\n@   "));
+        for _ in 0..animation.frames() {
+            animation.step();
+        }
+        let screen = Ten::part2(&input).to_string();
+        assert!(animation.draw(80, 24).contains(screen.trim_end()));
+    }
+
+    #[test]
+    fn repl_runs_cycles() {
+        let mut session = Ten::session(Ten::parse(EXAMPLE).unwrap());
+        assert_eq!(
+            session.run("step", &["19"]).unwrap(),
+            "cycle 20, X = 21, signal strength so far 0"
+        );
+        assert_eq!(
+            session.run("step", &[]).unwrap(),
+            "cycle 21, X = 21, signal strength so far 420"
+        );
+        assert!(session
+            .run("show", &[])
+            .unwrap()
+            .contains("\n##..##..##..##..##..@"));
+        assert!(session
+            .run("step", &["1000"])
+            .unwrap()
+            .ends_with("signal strength so far 13140, program finished"));
+    }
+
+    #[test]
+    fn big_operands_are_rejected_not_overflowed() {
+        assert_eq!(Ten::parse("noop\naddx 2147483648\n").unwrap_err().line, 2);
+        let input = Ten::parse(&"addx 2147483647\n".repeat(150)).unwrap();
+        assert!(Ten::part1(&input) > 0);
+        Ten::part2(&input);
+    }
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(Ten::part1(&Ten::parse(EXAMPLE).unwrap()), 13140);
+    }
+
+    #[test]
+    fn part2_example() {
+        let screen = Answer::from(Ten::part2(&Ten::parse(EXAMPLE).unwrap()));
+        assert_eq!(
+            screen.to_string(),
+            "##..##..##..##..##..##..##..##..##..##..
+###...###...###...###...###...###...###.
+####....####....####....####....####....
+#####.....#####.....#####.....#####.....
+######......######......######......####
+#######.......#######.......#######....."
+        );
+    }
+
+    #[test]
+    fn small_program_register() {
+        let comp = Computer::run(&Ten::parse("noop\naddx 3\naddx -5\n").unwrap());
+        assert_eq!(comp.cycle, 6);
+        assert_eq!(comp.x, -1);
+    }
+
+    #[test]
+    fn negative_register_signal() {
+        let comp = Computer::run(&Ten::parse(&"addx -3\n".repeat(10)).unwrap());
+        assert_eq!(comp.result, 20 * (1 - 3 * 9));
+    }
+
+    #[test]
+    fn long_programs_stop_drawing() {
+        let comp = Computer::run(&Ten::parse(&"noop\n".repeat(300)).unwrap());
+        assert_eq!(comp.screen.y, 6);
+    }
+
+    #[test]
+    fn streaming_matches_parsing() {
+        let parsed = Ten::parse(EXAMPLE).unwrap();
+        let (result, screen) = Ten::stream(&mut LineReader::new(&mut EXAMPLE.as_bytes())).unwrap();
+        assert_eq!(result, Ten::part1(&parsed));
+        assert_eq!(Answer::from(screen), Answer::from(Ten::part2(&parsed)));
+    }
+
+    #[test]
+    fn generated_program_matches_its_register() {
+        for seed in 1..=5 {
+            let ops = Ten::parse(&DAY.generate(seed, 200)).unwrap();
+            // X during each cycle, worked out directly
+            let mut x = 1;
+            let mut during = Vec::new();
+            for op in &ops {
+                during.extend(std::iter::repeat_n(x, op.cycles()));
+                if let Op::AddX(val) = op {
+                    x += val;
+                }
+            }
+            let strength = [20, 60, 100, 140, 180, 220]
+                .into_iter()
+                .filter(|&cycle| cycle <= during.len())
+                .map(|cycle| cycle as isize * during[cycle - 1])
+                .sum::<isize>();
+            assert_eq!(Ten::part1(&ops), strength, "seed {seed}");
+            let screen = Ten::part2(&ops);
+            for (cycle, &x) in during.iter().enumerate().take(WIDTH * HEIGHT) {
+                let column = (cycle % WIDTH) as isize;
+                let lit = (x - column).abs() <= 1;
+                let pixel = screen.pixels[(cycle % WIDTH, cycle / WIDTH)];
+                assert_eq!(pixel == '#', lit, "seed {seed}, cycle {}", cycle + 1);
+            }
+        }
+    }
+
+    fn op() -> impl Strategy<Value = Op> {
+        prop_oneof![
+            Just(Op::Noop),
+            any::<i32>().prop_map(|val| Op::AddX(val as isize))
+        ]
+    }
+
+    proptest! {
+        #[test]
+        fn op_round_trips(op in op()) {
+            prop_assert_eq!(op.to_string().parse::<Op>().unwrap(), op);
+        }
+
+        #[test]
+        fn arbitrary_bytes_never_panic(bytes in prop::collection::vec(any::<u8>(), 0..24)) {
+            let s = String::from_utf8_lossy(&bytes);
+            if let Ok(op) = s.parse::<Op>() {
+                prop_assert_eq!(op.to_string().parse::<Op>().unwrap(), op);
+            }
+        }
+
+        #[test]
+        fn op_like_input_never_panics(s in "(noop|addx)( [+-]?[0-9a-z]{0,24}){0,2}") {
+            if let Ok(op) = s.parse::<Op>() {
+                prop_assert_eq!(op.to_string().parse::<Op>().unwrap(), op);
+            }
+        }
+    }
+}
@@ -0,0 +1,242 @@
+use anyhow::Result;
+use aoc_common::lines;
+use aoc_common::Day;
+use aoc_common::Generator;
+use aoc_common::Line;
+use aoc_common::LineReader;
+use aoc_common::ParseError;
+use aoc_common::Rng;
+use aoc_common::Solution;
+use aoc_common::Stream;
+use std::collections::HashSet;
+use std::io;
+use std::io::Write;
+use tracing::debug;
+use tracing::trace;
+
+pub const DAY: Day = Day::new::<Three>(3, "three").with_stream::<Three>();
+
+pub struct Three;
+
+impl Solution for Three {
+    type Input = Vec<String>;
+    type Output1 = u64;
+    type Output2 = u64;
+    // 2: rejects rucksacks and groups without exactly one shared item
+    const VERSION: &'static str = "2";
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        let rucks = read_input(input)?;
+        debug!(rucksacks = rucks.len(), "read rucksacks");
+        Ok(rucks)
+    }
+
+    fn part1(rucks: &Self::Input) -> u64 {
+        rucks.iter().map(|ruck| misplaced(ruck)).sum()
+    }
+
+    fn part2(rucks: &Self::Input) -> u64 {
+        let mut badge_score = 0;
+        for chunk in rucks.chunks(3) {
+            if let [first, second, third] = chunk {
+                badge_score += badge(first, second, third);
+            }
+        }
+        badge_score
+    }
+}
+
+impl Stream for Three {
+    /// Only the current group of three rucksacks is kept.
+    fn stream(lines: &mut LineReader<'_>) -> Result<(u64, u64)> {
+        let (mut score, mut badge_score) = (0, 0);
+        let mut group: [String; 3] = Default::default();
+        let mut rucks = 0;
+        while let Some(line) = lines.next_line()? {
+            let ruck = read_ruck(&line)?;
+            score += misplaced(ruck);
+            let member = &mut group[rucks % 3];
+            member.clear();
+            member.push_str(ruck);
+            rucks += 1;
+            if rucks % 3 == 0 {
+                check_badge(&line, &group)?;
+                badge_score += badge(&group[0], &group[1], &group[2]);
+            }
+        }
+        debug!(rucksacks = rucks, "streamed rucksacks");
+        Ok((score, badge_score))
+    }
+}
+
+const ITEMS: &[u8] = b"abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ";
+
+impl Generator for Three {
+    const UNIT: &'static str = "rucksacks, rounded up to a whole group";
+
+    fn generate(rng: &mut Rng, size: usize, out: &mut dyn Write) -> io::Result<()> {
+        for _ in 0..size.div_ceil(3) {
+            // Each letter has exactly one use in a group: the badge, the item
+            // shared by one rucksack's compartments, or filler private to one
+            // compartment
+            let mut items = ITEMS.to_vec();
+            rng.shuffle(&mut items);
+            let badge = items[0];
+            let (shared, pools) = items[1..].split_at(3);
+            for (&shared, pool) in shared.iter().zip(pools.chunks(16)) {
+                let (first_pool, second_pool) = pool.split_at(8);
+                let filler = rng.below(12);
+                let mut first = vec![badge, shared];
+                first.extend((0..filler).map(|_| rng.pick(first_pool)));
+                let mut second = vec![shared];
+                second.extend((0..=filler).map(|_| rng.pick(second_pool)));
+                rng.shuffle(&mut first);
+                rng.shuffle(&mut second);
+                out.write_all(&first)?;
+                out.write_all(&second)?;
+                writeln!(out)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The priority of the item in both of the rucksack's compartments.
+fn misplaced(ruck: &str) -> u64 {
+    let (first, second) = split(ruck);
+    // Parsing made sure there's exactly one
+    let d = shared(&[first, second])[0];
+    trace!(item = %d, priority = value(d), "misplaced item");
+    value(d)
+}
+
+/// The priority of the group's badge, the one item all three carry.
+fn badge(first: &str, second: &str, third: &str) -> u64 {
+    let c = shared(&[first, second, third])[0];
+    trace!(badge = %c, priority = value(c), "group badge");
+    value(c)
+}
+
+/// The items every one of `rucks` holds.
+fn shared<S: AsRef<str>>(rucks: &[S]) -> Vec<char> {
+    let mut items = rucks[0].as_ref().chars().collect::<HashSet<_>>();
+    for ruck in &rucks[1..] {
+        let other = ruck.as_ref().chars().collect::<HashSet<_>>();
+        items.retain(|item| other.contains(item));
+    }
+    items.into_iter().collect()
+}
+
+fn value(c: char) -> u64 {
+    match c {
+        val @ 'a'..='z' => (val as u64 - 97) + 1,
+        val @ 'A'..='Z' => (val as u64 - 65) + 27,
+        _ => panic!("Invalid value {c}"),
+    }
+}
+
+fn split(ruck: &str) -> (&str, &str) {
+    let len = ruck.len();
+    (&ruck[0..len / 2], &ruck[len / 2..len])
+}
+
+fn read_input(input: &str) -> Result<Vec<String>, ParseError> {
+    let mut rucks = Vec::new();
+    for line in lines(input) {
+        rucks.push(read_ruck(&line)?.to_string());
+        if rucks.len().is_multiple_of(3) {
+            check_badge(&line, &rucks[rucks.len() - 3..])?;
+        }
+    }
+    Ok(rucks)
+}
+
+fn read_ruck<'a>(line: &Line<'a>) -> Result<&'a str, ParseError> {
+    let ruck = line.text.trim();
+    if let Some(pos) = ruck.find(|c: char| !c.is_ascii_alphabetic()) {
+        return Err(line.error_at(&ruck[pos..pos + 1], "expected an item letter"));
+    }
+    if ruck.is_empty() {
+        return Err(line.error("expected a rucksack"));
+    }
+    if !ruck.len().is_multiple_of(2) {
+        return Err(line.error_at(ruck, "rucksack has an odd number of items"));
+    }
+    let (first, second) = split(ruck);
+    match shared(&[first, second]).len() {
+        1 => Ok(ruck),
+        n => Err(line.error_at(
+            ruck,
+            format!("compartments share {n} kinds of item, expected one"),
+        )),
+    }
+}
+
+/// Checks the group ending at `line` has exactly one badge.
+fn check_badge(line: &Line<'_>, group: &[String]) -> Result<(), ParseError> {
+    match shared(group).len() {
+        1 => Ok(()),
+        n => Err(line.error(format!(
+            "the group's rucksacks share {n} kinds of item, expected one badge"
+        ))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "vJrwpWtwJgWrhcsFMMfFFhFp
+jqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL
+PmmdzqPrVvPwwTWBwg
+wMqvLMZHhHMvwLHjbvcjnnSBnvTQFn
+ttgJtRGJQctTZtZT
+CrZsJsPPZsGzwwsLwLmpwMDw
+";
+
+    #[test]
+    fn rejects_rucksacks_without_one_answer() {
+        let line = |input: &str| Three::parse(input).unwrap_err().line;
+        assert_eq!(line("aa\n\nbb\n"), 2);
+        assert_eq!(line("aa\nab\n"), 2);
+        assert_eq!(line("aa\nabab\n"), 2);
+        assert_eq!(line("aa\nbb\ncc\n"), 3);
+        let streamed = Three::stream(&mut LineReader::new(&mut "aa\nbb\ncc\n".as_bytes()));
+        assert_eq!(
+            streamed.unwrap_err().downcast::<ParseError>().unwrap().line,
+            3
+        );
+    }
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(Three::part1(&Three::parse(EXAMPLE).unwrap()), 157);
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(Three::part2(&Three::parse(EXAMPLE).unwrap()), 70);
+    }
+
+    #[test]
+    fn streaming_matches_parsing() {
+        for input in [EXAMPLE.to_string(), DAY.generate(1, 200)] {
+            let parsed = Three::parse(&input).unwrap();
+            let streamed = Three::stream(&mut LineReader::new(&mut input.as_bytes())).unwrap();
+            assert_eq!(streamed, (Three::part1(&parsed), Three::part2(&parsed)));
+        }
+    }
+
+    #[test]
+    fn generated_priorities_stay_in_range() {
+        for seed in 1..=5 {
+            // Parsing checks every rucksack and group has its one item
+            let rucks = Three::parse(&DAY.generate(seed, 200)).unwrap();
+            assert_eq!(rucks.len(), 201);
+            let misplaced = Three::part1(&rucks);
+            assert!((201..=52 * 201).contains(&misplaced), "seed {seed}");
+            let badges = Three::part2(&rucks);
+            assert!((67..=52 * 67).contains(&badges), "seed {seed}");
+        }
+    }
+}
@@ -0,0 +1,307 @@
+use anyhow::anyhow;
+use anyhow::Error;
+use anyhow::Result;
+use aoc_common::lines;
+use aoc_common::Day;
+use aoc_common::Generator;
+use aoc_common::Line;
+use aoc_common::LineReader;
+use aoc_common::ParseError;
+use aoc_common::Rng;
+use aoc_common::Solution;
+use aoc_common::Stream;
+use std::fmt;
+use std::io;
+use std::io::Write;
+use std::str::FromStr;
+use tracing::debug;
+
+const WIN: u64 = 6;
+const DRAW: u64 = 3;
+const LOSE: u64 = 0;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Strat {
+    Lose,
+    Draw,
+    Win,
+}
+
+impl FromStr for Strat {
+    type Err = Error;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let out = match s {
+            "X" => Strat::Lose,
+            "Y" => Strat::Draw,
+            "Z" => Strat::Win,
+            _ => return Err(anyhow!("Invalid character: {s}")),
+        };
+        Ok(out)
+    }
+}
+
+impl fmt::Display for Strat {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let val = match self {
+            Strat::Lose => "X",
+            Strat::Draw => "Y",
+            Strat::Win => "Z",
+        };
+        write!(f, "{val}")
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RPS {
+    Rock,
+    Paper,
+    Scissors,
+}
+
+impl RPS {
+    fn wins_against(&self) -> RPS {
+        match self {
+            RPS::Rock => RPS::Scissors,
+            RPS::Paper => RPS::Rock,
+            RPS::Scissors => RPS::Paper,
+        }
+    }
+    fn loses_against(&self) -> RPS {
+        match self {
+            RPS::Rock => RPS::Paper,
+            RPS::Paper => RPS::Scissors,
+            RPS::Scissors => RPS::Rock,
+        }
+    }
+}
+
+impl From<RPS> for u64 {
+    fn from(s: RPS) -> u64 {
+        match s {
+            RPS::Rock => 1,
+            RPS::Paper => 2,
+            RPS::Scissors => 3,
+        }
+    }
+}
+
+impl FromStr for RPS {
+    type Err = Error;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let out = match s {
+            "A" | "X" => RPS::Rock,
+            "B" | "Y" => RPS::Paper,
+            "C" | "Z" => RPS::Scissors,
+            _ => return Err(anyhow!("Invalid character: {s}")),
+        };
+        Ok(out)
+    }
+}
+
+/// Formats as the opponent's column, `A`, `B` or `C`.
+impl fmt::Display for RPS {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let val = match self {
+            RPS::Rock => "A",
+            RPS::Paper => "B",
+            RPS::Scissors => "C",
+        };
+        write!(f, "{val}")
+    }
+}
+
+pub const DAY: Day = Day::new::<Two>(2, "two").with_stream::<Two>();
+
+pub struct Two;
+
+impl Solution for Two {
+    /// Each round's opponent shape with the second column read both ways:
+    /// as a shape for part 1 and as an outcome for part 2.
+    type Input = Vec<(RPS, RPS, Strat)>;
+    type Output1 = u64;
+    type Output2 = u64;
+    // 2: reads each round in one pass
+    const VERSION: &'static str = "2";
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        let rounds = read_input(input)?;
+        debug!(rounds = rounds.len(), "read strategy guide");
+        Ok(rounds)
+    }
+
+    fn part1(rounds: &Self::Input) -> u64 {
+        rounds
+            .iter()
+            .map(|&(first, second, _)| calc_rps(first, second))
+            .sum()
+    }
+
+    fn part2(rounds: &Self::Input) -> u64 {
+        rounds
+            .iter()
+            .map(|&(first, _, outcome)| calc_strat(first, outcome))
+            .sum()
+    }
+}
+
+impl Stream for Two {
+    fn stream(lines: &mut LineReader<'_>) -> Result<(u64, u64)> {
+        let (mut rps, mut strat) = (0, 0);
+        let mut rounds = 0;
+        while let Some(line) = lines.next_line()? {
+            if let Some((first, second, outcome)) = read_round(&line)? {
+                rps += calc_rps(first, second);
+                strat += calc_strat(first, outcome);
+                rounds += 1;
+            }
+        }
+        debug!(rounds, "streamed strategy guide");
+        Ok((rps, strat))
+    }
+}
+
+impl Generator for Two {
+    const UNIT: &'static str = "rounds";
+
+    fn generate(rng: &mut Rng, size: usize, out: &mut dyn Write) -> io::Result<()> {
+        for _ in 0..size {
+            writeln!(
+                out,
+                "{} {}",
+                rng.pick(&['A', 'B', 'C']),
+                rng.pick(&['X', 'Y', 'Z'])
+            )?;
+        }
+        Ok(())
+    }
+}
+
+fn calc_strat(first: RPS, second: Strat) -> u64 {
+    match second {
+        Strat::Draw => <RPS as Into<u64>>::into(first) + DRAW,
+        Strat::Lose => <RPS as Into<u64>>::into(first.wins_against()) + LOSE,
+        Strat::Win => <RPS as Into<u64>>::into(first.loses_against()) + WIN,
+    }
+}
+
+fn calc_rps(first: RPS, second: RPS) -> u64 {
+    if first == second {
+        return <RPS as Into<u64>>::into(second) + DRAW;
+    }
+    if first == RPS::Rock && second == RPS::Paper
+        || first == RPS::Paper && second == RPS::Scissors
+        || first == RPS::Scissors && second == RPS::Rock
+    {
+        return <RPS as Into<u64>>::into(second) + WIN;
+    }
+    <RPS as Into<u64>>::into(second) + LOSE
+}
+
+fn read_input(input: &str) -> Result<Vec<(RPS, RPS, Strat)>, ParseError> {
+    let mut rounds = Vec::new();
+    for line in lines(input) {
+        rounds.extend(read_round(&line)?);
+    }
+    Ok(rounds)
+}
+
+/// One round, with the second column read both ways. Blank lines are
+/// skipped.
+fn read_round(line: &Line) -> Result<Option<(RPS, RPS, Strat)>, ParseError> {
+    if line.text.is_empty() {
+        return Ok(None);
+    }
+    if let [first, second] = line.words()[..] {
+        Ok(Some((
+            line.parse::<RPS>(first)?,
+            line.parse::<RPS>(second)?,
+            line.parse::<Strat>(second)?,
+        )))
+    } else {
+        Err(line.error("expected two columns"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    const EXAMPLE: &str = "A Y
+B X
+C Z
+";
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(Two::part1(&Two::parse(EXAMPLE).unwrap()), 15);
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(Two::part2(&Two::parse(EXAMPLE).unwrap()), 12);
+    }
+
+    #[test]
+    fn streaming_matches_parsing() {
+        for input in [EXAMPLE.to_string(), DAY.generate(1, 200)] {
+            let parsed = Two::parse(&input).unwrap();
+            let streamed = Two::stream(&mut LineReader::new(&mut input.as_bytes())).unwrap();
+            assert_eq!(streamed, (Two::part1(&parsed), Two::part2(&parsed)));
+        }
+    }
+
+    #[test]
+    fn errors_point_at_their_line() {
+        let line = |input: &str| Two::parse(input).unwrap_err().line;
+        assert_eq!(line("A Y\nB W\n"), 2);
+        assert_eq!(line("A Y\n\nC\n"), 3);
+        // Z is a shape and an outcome, but D is neither
+        assert_eq!(line("A Z\nD X\n"), 2);
+    }
+
+    #[test]
+    fn generated_scores_stay_in_range() {
+        for seed in 1..=5 {
+            let input = Two::parse(&DAY.generate(seed, 200)).unwrap();
+            assert_eq!(input.len(), 200);
+            // A round scores at least 1 for rock and at most 6 + 3 for a win
+            // with scissors
+            for score in [Two::part1(&input), Two::part2(&input)] {
+                assert!((200..=9 * 200).contains(&score), "seed {seed}: {score}");
+            }
+        }
+    }
+
+    fn rps() -> impl Strategy<Value = RPS> {
+        prop::sample::select(vec![RPS::Rock, RPS::Paper, RPS::Scissors])
+    }
+
+    fn strat() -> impl Strategy<Value = Strat> {
+        prop::sample::select(vec![Strat::Lose, Strat::Draw, Strat::Win])
+    }
+
+    proptest! {
+        #[test]
+        fn rps_round_trips(rps in rps()) {
+            prop_assert_eq!(rps.to_string().parse::<RPS>().unwrap(), rps);
+        }
+
+        #[test]
+        fn strat_round_trips(strat in strat()) {
+            prop_assert_eq!(strat.to_string().parse::<Strat>().unwrap(), strat);
+        }
+
+        #[test]
+        fn arbitrary_bytes_never_panic(bytes in prop::collection::vec(any::<u8>(), 0..8)) {
+            let s = String::from_utf8_lossy(&bytes);
+            prop_assert_eq!(s.parse::<RPS>().is_ok(), ["A", "B", "C", "X", "Y", "Z"].contains(&&*s));
+            prop_assert_eq!(s.parse::<Strat>().is_ok(), ["X", "Y", "Z"].contains(&&*s));
+        }
+
+        #[test]
+        fn arbitrary_input_never_panics(s in "\\PC*") {
+            let _ = Two::parse(&s);
+        }
+    }
+}